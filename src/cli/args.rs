@@ -0,0 +1,83 @@
+use clap::{Parser, ValueEnum};
+
+use crate::model::update_options::VersionType;
+
+#[derive(Parser, Debug, Clone)]
+#[command(version, about)]
+pub struct Args {
+    /// Check for updates without the interactive UI.
+    ///
+    /// Exits with status 1 when outdated dependencies are found, and 2 on errors,
+    /// including dependencies whose versions could not be looked up.
+    #[arg(long)]
+    pub check: bool,
+
+    /// Which kinds of updates count as outdated in `--check` mode.
+    #[arg(
+        long,
+        value_name = "TYPES",
+        value_delimiter = ',',
+        default_value = "major,minor,patch"
+    )]
+    pub fail_on: Vec<VersionType>,
+
+    /// Output format of the headless report. Anything other than `text` implies `--check`.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+
+    /// Apply updates without the interactive UI, following the `--apply` policy.
+    #[arg(short, long, conflicts_with_all = ["check", "format"])]
+    pub yes: bool,
+
+    /// Which kinds of updates to apply unattended. The largest allowed update is
+    /// chosen for each dependency. Implies `--yes`. [default: minor,patch]
+    #[arg(
+        long,
+        value_name = "TYPES",
+        value_delimiter = ',',
+        conflicts_with_all = ["check", "format"]
+    )]
+    pub apply: Option<Vec<VersionType>>,
+
+    /// Print a unified diff of every file that would change, instead of writing it.
+    #[arg(long, conflicts_with_all = ["check", "format"])]
+    pub dry_run: bool,
+
+    /// Answer only from the local version cache, without touching the network.
+    /// Versions from expired cache entries are marked as stale.
+    #[arg(long)]
+    pub offline: bool,
+
+    /// Hold back updates published fewer than this many days ago, overriding
+    /// `min-age-days` from the config.
+    #[arg(long, value_name = "DAYS")]
+    pub min_age_days: Option<u64>,
+}
+
+impl Args {
+    /// Whether to skip the interactive UI and print a report instead.
+    pub fn is_headless(&self) -> bool {
+        self.check || self.format != OutputFormat::Text
+    }
+
+    /// Whether to skip the interactive UI and apply updates according to the policy.
+    pub fn is_unattended(&self) -> bool {
+        self.yes || self.apply.is_some()
+    }
+
+    pub fn apply_policy(&self) -> Vec<VersionType> {
+        self.apply
+            .clone()
+            .unwrap_or_else(|| vec![VersionType::Minor, VersionType::Patch])
+    }
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// A plain, human-readable report.
+    Text,
+    /// A single JSON document listing every dependency.
+    Json,
+    /// One JSON object per dependency, per line.
+    Ndjson,
+}
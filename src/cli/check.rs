@@ -0,0 +1,266 @@
+use std::{path::Path, time::Duration};
+
+use itertools::Itertools;
+
+use super::{entry_map::Entry, load_entry_map, report::Report, EntryMap, OutputFormat};
+use crate::{
+    model::{update_options::VersionType, Artifact, Group},
+    package_search::maven::MavenPackageSearch,
+};
+
+pub const EXIT_UP_TO_DATE: i32 = 0;
+pub const EXIT_OUTDATED: i32 = 1;
+pub const EXIT_ERROR: i32 = 2;
+
+/// Runs the headless update check, printing a report in the given format to stdout.
+/// Returns the exit code the process should terminate with.
+pub async fn run(
+    project_path: &Path,
+    maven_package_search: &MavenPackageSearch,
+    fail_on: &[VersionType],
+    format: OutputFormat,
+    min_age: Duration,
+) -> i32 {
+    let entry_map = match load_entry_map(project_path, maven_package_search, min_age).await {
+        Ok(entry_map) => entry_map,
+        Err(err) => {
+            eprintln!("error: {:#}", err);
+            return EXIT_ERROR;
+        }
+    };
+
+    let output = match format {
+        OutputFormat::Text => Ok(render_report(&entry_map, fail_on)),
+        OutputFormat::Json => {
+            Report::new(project_path, &entry_map, fail_on).and_then(|r| r.to_json())
+        }
+        OutputFormat::Ndjson => {
+            Report::new(project_path, &entry_map, fail_on).and_then(|r| r.to_ndjson())
+        }
+    };
+    match output {
+        Ok(output) if format == OutputFormat::Json => println!("{}", output),
+        Ok(output) => print!("{}", output),
+        Err(err) => {
+            eprintln!("error: {:#}", err);
+            return EXIT_ERROR;
+        }
+    }
+
+    exit_code(&entry_map, fail_on)
+}
+
+/// Dependencies that could not be checked fail the run, outdated or not, as
+/// otherwise a run whose every lookup failed would pass as up to date.
+fn exit_code(entry_map: &EntryMap, fail_on: &[VersionType]) -> i32 {
+    if !entry_map.with_errors().is_empty() {
+        EXIT_ERROR
+    } else if outdated(entry_map, fail_on).is_empty() {
+        EXIT_UP_TO_DATE
+    } else {
+        EXIT_OUTDATED
+    }
+}
+
+/// The entries with at least one update of the given types.
+pub fn outdated(entry_map: &EntryMap, fail_on: &[VersionType]) -> Vec<(Group, Artifact, Entry)> {
+    entry_map
+        .with_updates()
+        .into_iter()
+        .filter(|(_, _, entry)| is_outdated(entry, fail_on))
+        .collect()
+}
+
+fn is_outdated(entry: &Entry, fail_on: &[VersionType]) -> bool {
+    entry
+        .update_options
+        .as_ref()
+        .is_some_and(|options| options.has_any(fail_on))
+}
+
+pub fn render_report(entry_map: &EntryMap, fail_on: &[VersionType]) -> String {
+    let entries = entry_map.with_updates();
+    let coordinates: Vec<String> = entries
+        .iter()
+        .map(|(group, artifact, _)| format!("{}:{}", group, artifact))
+        .collect();
+    let coordinate_width = coordinates.iter().map(|c| c.len()).max().unwrap_or(0);
+    let version_width = entries
+        .iter()
+        .map(|(_, _, entry)| entry.version.to_string().len())
+        .max()
+        .unwrap_or(0);
+
+    let mut report = String::new();
+    for ((_, _, entry), coordinate) in entries.iter().zip(coordinates) {
+        let status = if is_outdated(entry, fail_on) {
+            "outdated"
+        } else if entry.relocation.is_some() {
+            "moved"
+        } else {
+            "ignored"
+        };
+        let options = entry
+            .update_options
+            .iter()
+            .flat_map(|options| {
+                VersionType::ALL
+                    .into_iter()
+                    .filter_map(move |vt| options.get(vt).map(|v| format!("{}: {}", vt, v)))
+            })
+            .chain(entry.relocation.iter().map(|relocation| {
+                format!(
+                    "Moved: {}:{} {}",
+                    relocation.group, relocation.artifact, relocation.version
+                )
+            }))
+            .join(", ");
+
+        report.push_str(&format!(
+            "{:<8}  {:<coordinate_width$}  {:>version_width$} -> {}\n",
+            status,
+            coordinate,
+            entry.version.to_string(),
+            options,
+        ));
+    }
+
+    let failures = entry_map.with_errors();
+    for (group, artifact, entry) in &failures {
+        report.push_str(&format!(
+            "error     {}:{}  {}\n",
+            group,
+            artifact,
+            entry.lookup_error.as_deref().unwrap_or_default()
+        ));
+    }
+
+    let outdated_count = entries
+        .iter()
+        .filter(|(_, _, entry)| is_outdated(entry, fail_on))
+        .count();
+    report.push_str(&format!(
+        "{} of {} dependencies outdated (failing on: {})\n",
+        outdated_count,
+        entry_map.map.len(),
+        fail_on.iter().join(", ")
+    ));
+    if !failures.is_empty() {
+        report.push_str(&format!("{} could not be checked\n", failures.len()));
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Relocation, Version};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_outdated_respects_fail_on() {
+        let entry_map = EntryMap::from_entries(vec![
+            Entry::with_versions("dev.zio", "zio", "2.0.0", &["2.0.1", "3.0.0"]),
+            Entry::with_versions("dev.zio", "zio-json", "0.6.0", &["1.0.0"]),
+            Entry::with_versions("org.postgresql", "postgresql", "42.5.1", &["42.5.1"]),
+        ]);
+
+        let patch_only = outdated(&entry_map, &[VersionType::Patch]);
+        assert_eq!(patch_only.len(), 1);
+        assert_eq!(patch_only[0].1, Artifact::new("zio"));
+
+        let major = outdated(&entry_map, &[VersionType::Major]);
+        assert_eq!(major.len(), 2);
+    }
+
+    #[test]
+    fn test_render_report() {
+        let entry_map = EntryMap::from_entries(vec![
+            Entry::with_versions("dev.zio", "zio", "2.0.0", &["2.0.1", "3.0.0"]),
+            Entry::with_versions("dev.zio", "zio-json", "0.6.0", &["1.0.0"]),
+            Entry::with_versions("org.postgresql", "postgresql", "42.5.1", &["42.5.1"]),
+        ]);
+
+        let report = render_report(&entry_map, &[VersionType::Patch]);
+        assert_eq!(
+            report,
+            "\
+outdated  dev.zio:zio       2.0.0 -> Major: 3.0.0, Patch: 2.0.1
+ignored   dev.zio:zio-json  0.6.0 -> Major: 1.0.0
+1 of 3 dependencies outdated (failing on: Patch)
+"
+        );
+    }
+
+    #[test]
+    fn test_render_report_with_failures() {
+        let mut failed = Entry::with_versions("org.typelevel", "cats-core", "2.9.0", &[]);
+        failed.update_options = None;
+        failed.lookup_error = Some("Request failed with status: 429".to_string());
+        let entry_map = EntryMap::from_entries(vec![
+            Entry::with_versions("dev.zio", "zio", "2.0.0", &["2.0.1"]),
+            failed,
+        ]);
+
+        let report = render_report(&entry_map, &[VersionType::Patch]);
+        assert_eq!(
+            report,
+            "\
+outdated  dev.zio:zio  2.0.0 -> Patch: 2.0.1
+error     org.typelevel:cats-core  Request failed with status: 429
+1 of 2 dependencies outdated (failing on: Patch)
+1 could not be checked
+"
+        );
+        assert_eq!(exit_code(&entry_map, &[VersionType::Patch]), EXIT_ERROR);
+    }
+
+    #[test]
+    fn test_exit_code() {
+        let up_to_date = EntryMap::from_entries(vec![Entry::with_versions(
+            "dev.zio",
+            "zio",
+            "2.0.0",
+            &["2.0.0"],
+        )]);
+        assert_eq!(
+            exit_code(&up_to_date, &[VersionType::Patch]),
+            EXIT_UP_TO_DATE
+        );
+
+        let outdated = EntryMap::from_entries(vec![Entry::with_versions(
+            "dev.zio",
+            "zio",
+            "2.0.0",
+            &["2.0.1"],
+        )]);
+        assert_eq!(exit_code(&outdated, &[VersionType::Patch]), EXIT_OUTDATED);
+
+        let mut failed = Entry::with_versions("dev.zio", "zio", "2.0.0", &[]);
+        failed.update_options = None;
+        failed.lookup_error = Some("dns error".to_string());
+        let all_failed = EntryMap::from_entries(vec![failed]);
+        assert_eq!(exit_code(&all_failed, &[VersionType::Patch]), EXIT_ERROR);
+    }
+
+    #[test]
+    fn test_render_report_with_relocation() {
+        let mut moved = Entry::with_versions("com.typesafe.sbt", "sbt-git", "1.0.2", &[]);
+        moved.relocation = Some(Relocation {
+            group: Group::new("com.github.sbt"),
+            artifact: Artifact::new("sbt-git"),
+            version: Version::new("2.1.0"),
+            message: None,
+        });
+        let entry_map = EntryMap::from_entries(vec![moved]);
+
+        let report = render_report(&entry_map, &[VersionType::Patch]);
+        assert_eq!(
+            report,
+            "\
+moved     com.typesafe.sbt:sbt-git  1.0.2 -> Moved: com.github.sbt:sbt-git 2.1.0
+0 of 1 dependencies outdated (failing on: Patch)
+"
+        );
+    }
+}
@@ -1,26 +1,119 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
 
 use itertools::Itertools;
 
-use crate::dependency_resolver::{DependencyMap, Location};
+use crate::dependency_resolver::{CoordinateLocation, CoordinateUpdate, DependencyMap, Location};
 use crate::model::{
-    update_options::{UpdateOptions, VersionType},
-    Artifact, Group, Version,
+    update_options::{MinAge, UpdateOptions, VersionType},
+    Artifact, CrossVersion, DependencyKey, DependencyKind, Group, ReleaseDate, Relocation, Version,
 };
+use crate::package_search::{ReleaseDates, VersionLookup};
 
 #[derive(Clone, Debug)]
 pub struct Entry {
     pub group: Group,
     pub artifact: Artifact,
+    /// The `major.minor` line it is kept on, for an artifact updated independently
+    /// on several lines, e.g. the Scala 2.12 and 2.13 versions of a cross build.
+    pub line: Option<String>,
+    pub kind: DependencyKind,
+    pub cross: CrossVersion,
     pub version: Version,
     pub locations: Vec<Location>,
     pub update_options: Option<UpdateOptions>,
     pub version_type: VersionType,
     pub is_selected: bool,
+    pub lookup_error: Option<String>,
+    /// Whether the available versions came from an expired cache entry.
+    pub is_stale: bool,
+    /// Every version the repositories offer.
+    pub available: Vec<Version>,
+    /// When the offered updates were published, `None` where no repository knows.
+    pub release_dates: HashMap<Version, Option<ReleaseDate>>,
+    /// Where the group and artifact are written, rewritten when migrating.
+    pub coordinates: Vec<CoordinateLocation>,
+    /// The new coordinates the artifact has moved to, if it has.
+    pub relocation: Option<Relocation>,
+    /// Whether to migrate to the relocation, rather than update the version.
+    pub migrate: bool,
+    /// The configurations it is declared in, e.g. `Test`.
+    pub configurations: Vec<String>,
+    /// The classifiers it is declared with, e.g. `tests`.
+    pub classifiers: Vec<String>,
 }
 
 impl Entry {
+    /// An entry of a library without any locations or lookup results yet.
+    pub fn new(group: Group, artifact: Artifact, version: Version) -> Entry {
+        Entry {
+            group,
+            artifact,
+            line: None,
+            kind: DependencyKind::Library,
+            cross: CrossVersion::Binary,
+            version,
+            locations: vec![],
+            update_options: None,
+            version_type: VersionType::Major,
+            is_selected: false,
+            lookup_error: None,
+            is_stale: false,
+            available: vec![],
+            release_dates: HashMap::new(),
+            coordinates: vec![],
+            relocation: None,
+            migrate: false,
+            configurations: vec![],
+            classifiers: vec![],
+        }
+    }
+
+    pub fn key(&self) -> DependencyKey {
+        DependencyKey {
+            group: self.group.clone(),
+            artifact: self.artifact.clone(),
+            line: self.line.clone(),
+        }
+    }
+
+    /// The updates on offer, only from the entry's line if it has one.
+    fn update_options(&self, min_age: Duration) -> Option<UpdateOptions> {
+        let min_age = MinAge {
+            min_age,
+            release_dates: &self.release_dates,
+            now: ReleaseDate::now(),
+        };
+        let available: Vec<Version> = self
+            .available
+            .iter()
+            .filter(|version| self.line.is_none() || version.line() == self.line)
+            .cloned()
+            .collect();
+        UpdateOptions::with_min_age(&self.version, &available, Some(&min_age))
+    }
+
+    /// When the given version was published, if known.
+    pub fn release_date(&self, version: &Version) -> Option<ReleaseDate> {
+        self.release_dates.get(version).copied().flatten()
+    }
+
+    /// Rewrites the group and artifact to the relocation's, if there is one.
+    pub fn coordinate_update(&self) -> Option<CoordinateUpdate> {
+        let relocation = self.relocation.as_ref()?;
+        Some(CoordinateUpdate {
+            group: relocation.group.clone(),
+            artifact: relocation.artifact.clone(),
+            coordinates: self.coordinates.clone(),
+        })
+    }
+
     pub fn current_update_version(&self) -> Option<&Version> {
+        if self.migrate {
+            return None;
+        }
         if let Some(update_options) = &self.update_options {
             match self.version_type {
                 VersionType::Major => update_options.major.as_ref(),
@@ -35,7 +128,9 @@ impl Entry {
 }
 
 pub struct EntryMap {
-    pub map: HashMap<(Group, Artifact), Entry>,
+    pub map: HashMap<DependencyKey, Entry>,
+    /// Updates published less than this long ago are held back.
+    pub min_age: Duration,
 }
 
 impl Default for EntryMap {
@@ -48,42 +143,59 @@ impl EntryMap {
     pub fn new() -> Self {
         Self {
             map: HashMap::new(),
+            min_age: Duration::ZERO,
         }
     }
 
+    /// Every entry with an update or a migration to offer, sorted by group and artifact.
     pub fn with_updates(&self) -> Vec<(Group, Artifact, Entry)> {
-        self.map
-            .iter()
-            .filter_map(|(dep, entry)| {
-                entry
-                    .update_options
-                    .as_ref()
-                    .map(|_| (dep.0.clone(), dep.1.clone(), entry.clone()))
-            })
-            .sorted_by(|(group, artifact, _), (b_group, b_artifact, _)| {
-                (group, artifact).cmp(&(b_group, b_artifact))
-            })
+        self.sorted()
+            .into_iter()
+            .filter(|(_, _, entry)| entry.update_options.is_some() || entry.relocation.is_some())
             .collect()
     }
 
-    pub fn get(&self, group: &Group, artifact: &Artifact) -> Option<&Entry> {
-        self.map.get(&(group.clone(), artifact.clone()))
+    pub fn get(&self, key: &DependencyKey) -> Option<&Entry> {
+        self.map.get(key)
+    }
+
+    pub fn get_mut(&mut self, key: &DependencyKey) -> Option<&mut Entry> {
+        self.map.get_mut(key)
     }
 
-    pub fn get_mut(&mut self, group: &Group, artifact: &Artifact) -> Option<&mut Entry> {
-        self.map.get_mut(&(group.clone(), artifact.clone()))
+    pub fn insert(&mut self, entry: Entry) {
+        self.map.insert(entry.key(), entry);
     }
 
-    pub fn insert(&mut self, group: Group, artifact: Artifact, entry: Entry) {
-        self.map.insert((group, artifact), entry);
+    pub fn remove(&mut self, key: &DependencyKey) -> Option<Entry> {
+        self.map.remove(key)
     }
 
-    pub fn remove(&mut self, group: &Group, artifact: &Artifact) -> Option<Entry> {
-        self.map.remove(&(group.clone(), artifact.clone()))
+    /// The entries of the given group and artifact, one per line it is kept on.
+    fn entries_of<'a>(
+        &'a mut self,
+        group: &'a Group,
+        artifact: &'a Artifact,
+    ) -> impl Iterator<Item = &'a mut Entry> {
+        self.map
+            .values_mut()
+            .filter(move |entry| entry.group == *group && entry.artifact == *artifact)
     }
 
-    pub fn groups_and_artifacts(&self) -> Vec<(Group, Artifact)> {
-        self.map.keys().cloned().collect()
+    /// Every group and artifact, along with how its versions should be resolved.
+    pub fn lookups(&self) -> Vec<(Group, Artifact, DependencyKind, CrossVersion)> {
+        self.map
+            .values()
+            .map(|entry| {
+                (
+                    entry.group.clone(),
+                    entry.artifact.clone(),
+                    entry.kind,
+                    entry.cross,
+                )
+            })
+            .unique_by(|(group, artifact, _, _)| (group.clone(), artifact.clone()))
+            .collect()
     }
 
     fn version_type_exists(update_options: &UpdateOptions, version_type: VersionType) -> bool {
@@ -96,29 +208,137 @@ impl EntryMap {
     }
 
     pub fn add_versions(&mut self, versions_map: &HashMap<(Group, Artifact), Vec<Version>>) {
-        versions_map
-            .iter()
-            .for_each(|((group, artifact), versions)| {
-                if let Some(entry) = self.get_mut(group, artifact) {
-                    if let Some(update_options) = UpdateOptions::new(&entry.version, versions) {
-                        // println!(
-                        //     "Update options for {:?}: {:?} current: {} all:  {}",
-                        //     (group, artifact),
-                        //     update_options,
-                        //     entry.version,
-                        //     versions.iter().join(", ")
-                        // );
-                        let version_type = Self::determine_version_type(&update_options);
-                        entry.update_options = Some(update_options);
-                        entry.version_type = version_type;
+        let min_age = self.min_age;
+        for ((group, artifact), versions) in versions_map {
+            for entry in self.entries_of(group, artifact) {
+                entry.available = versions.clone();
+                if let Some(update_options) = entry.update_options(min_age) {
+                    let version_type = Self::determine_version_type(&update_options);
+                    entry.update_options = Some(update_options);
+                    entry.version_type = version_type;
+                }
+            }
+        }
+    }
+
+    /// Like [`EntryMap::add_versions`], additionally recording failed lookups on their entries.
+    pub fn add_lookups(&mut self, lookups: &HashMap<(Group, Artifact), VersionLookup>) {
+        let mut versions_map = HashMap::new();
+        for ((group, artifact), lookup) in lookups {
+            for entry in self.entries_of(group, artifact) {
+                entry.is_stale = lookup.stale;
+                if let Err(err) = &lookup.versions {
+                    entry.lookup_error = Some(err.clone());
+                }
+            }
+            if let Ok(versions) = &lookup.versions {
+                versions_map.insert((group.clone(), artifact.clone()), versions.clone());
+            }
+        }
+        self.add_versions(&versions_map);
+    }
+
+    /// The entries whose versions could not be looked up, sorted by group and artifact.
+    pub fn with_errors(&self) -> Vec<(Group, Artifact, Entry)> {
+        self.sorted()
+            .into_iter()
+            .filter(|(_, _, entry)| entry.lookup_error.is_some())
+            .collect()
+    }
+
+    /// The offered updates whose release dates have not been looked up yet.
+    pub fn release_date_lookups(
+        &self,
+    ) -> Vec<(Group, Artifact, DependencyKind, CrossVersion, Vec<Version>)> {
+        self.with_updates()
+            .into_iter()
+            .filter_map(|(group, artifact, entry)| {
+                let versions: Vec<Version> = entry
+                    .update_options
+                    .iter()
+                    .flat_map(|options| options.versions())
+                    .filter(|version| !entry.release_dates.contains_key(version))
+                    .cloned()
+                    .collect();
+                (!versions.is_empty()).then_some((
+                    group,
+                    artifact,
+                    entry.kind,
+                    entry.cross,
+                    versions,
+                ))
+            })
+            .collect()
+    }
+
+    /// Records release dates and offers the updates again, holding back those
+    /// published less than the min age ago. This may offer versions that were
+    /// hidden behind a held back one, whose dates then need looking up in turn.
+    pub fn add_release_dates(&mut self, dates: &ReleaseDates) {
+        let min_age = self.min_age;
+        for ((group, artifact), dates) in dates {
+            for entry in self.entries_of(group, artifact) {
+                entry.release_dates.extend(dates.clone());
+                entry.update_options = entry.update_options(min_age);
+                match &entry.update_options {
+                    Some(update_options)
+                        if !Self::version_type_exists(update_options, entry.version_type) =>
+                    {
+                        entry.version_type = Self::determine_version_type(update_options);
                     }
-                } else {
-                    panic!(
-                        "Entry for group {:?} and artifact {:?} does not exist",
-                        group, artifact
-                    );
+                    Some(_) => (),
+                    None if entry.relocation.is_some() => entry.migrate = true,
+                    None => entry.is_selected = false,
                 }
-            });
+            }
+        }
+    }
+
+    /// The entries that could have moved, with their newest known version, whose
+    /// POM may say where to. Only entries whose coordinates can be rewritten count.
+    pub fn relocation_lookups(
+        &self,
+    ) -> Vec<(
+        Group,
+        Artifact,
+        DependencyKind,
+        CrossVersion,
+        Option<Version>,
+    )> {
+        self.map
+            .values()
+            .filter(|entry| !entry.coordinates.is_empty() && entry.lookup_error.is_none())
+            .map(|entry| {
+                let latest = entry.available.iter().max().cloned();
+                (
+                    entry.group.clone(),
+                    entry.artifact.clone(),
+                    entry.kind,
+                    entry.cross,
+                    latest,
+                )
+            })
+            .collect()
+    }
+
+    /// Records where artifacts have moved to. Migrating is the only option
+    /// offered for entries without version updates.
+    pub fn add_relocations(&mut self, relocations: &HashMap<(Group, Artifact), Relocation>) {
+        for ((group, artifact), relocation) in relocations {
+            for entry in self.entries_of(group, artifact) {
+                entry.relocation = Some(relocation.clone());
+                entry.migrate = entry.update_options.is_none();
+            }
+        }
+    }
+
+    /// Every entry, sorted by group, artifact and line.
+    pub fn sorted(&self) -> Vec<(Group, Artifact, Entry)> {
+        self.map
+            .iter()
+            .sorted_by(|(key, _), (b_key, _)| key.cmp(b_key))
+            .map(|(key, entry)| (key.group.clone(), key.artifact.clone(), entry.clone()))
+            .collect()
     }
 
     fn determine_version_type(update_options: &UpdateOptions) -> VersionType {
@@ -135,21 +355,18 @@ impl EntryMap {
 
     pub fn from_dependency_map(dependencies: &DependencyMap) -> EntryMap {
         let mut entry_map = EntryMap::new();
-        for ((group, artifact), version_with_locations) in dependencies.iter() {
+        for (key, version_with_locations) in dependencies.iter() {
             let version = version_with_locations.version.clone();
-            entry_map.insert(
-                group.clone(),
-                artifact.clone(),
-                Entry {
-                    group: group.clone(),
-                    artifact: artifact.clone(),
-                    version,
-                    locations: version_with_locations.locations.clone(),
-                    update_options: None,
-                    version_type: VersionType::Major,
-                    is_selected: false,
-                },
-            );
+            entry_map.insert(Entry {
+                line: key.line.clone(),
+                kind: version_with_locations.kind,
+                cross: version_with_locations.cross,
+                locations: version_with_locations.locations.clone(),
+                coordinates: version_with_locations.coordinates.clone(),
+                configurations: version_with_locations.configurations.clone(),
+                classifiers: version_with_locations.classifiers.clone(),
+                ..Entry::new(key.group.clone(), key.artifact.clone(), version)
+            });
         }
         entry_map
     }
@@ -158,8 +375,9 @@ impl EntryMap {
     pub fn selected(
         &self,
     ) -> impl Iterator<Item = (&Group, &Artifact, &Version, &Version, &Vec<Location>)> {
-        self.map.iter().filter_map(|((group, artifact), entry)| {
-            if entry.is_selected {
+        self.map.values().filter_map(|entry| {
+            let (group, artifact) = (&entry.group, &entry.artifact);
+            if entry.is_selected && !entry.migrate {
                 entry.update_options.as_ref().and_then(|update_options| {
                     let version = match entry.version_type {
                         VersionType::Major => update_options.major.as_ref(),
@@ -175,13 +393,110 @@ impl EntryMap {
         })
     }
 
-    fn for_each_shared_entry<F>(&mut self, group: &Group, artifact: &Artifact, mut f: F)
+    /// The selected entries set to migrate to their relocation.
+    pub fn selected_migrations(&self) -> impl Iterator<Item = &Entry> {
+        self.map
+            .values()
+            .filter(|entry| entry.is_selected && entry.migrate && entry.relocation.is_some())
+    }
+
+    /// Selects every entry with an allowed update, choosing the largest allowed
+    /// update type for each. Entries sharing a version, e.g. through a `val`, are
+    /// all set to the same version, the one preferred among those every one of
+    /// them is offered. Those without an update in common are left alone, and
+    /// returned.
+    pub fn select_by_policy(&mut self, allowed: &[VersionType]) -> Vec<(Group, Artifact)> {
+        let allowed_types = || {
+            VersionType::ALL
+                .into_iter()
+                .filter(|version_type| allowed.contains(version_type))
+        };
+        let offered = |entry: &Entry, version: &Version| {
+            entry.update_options.as_ref().and_then(|options| {
+                allowed_types().find(|version_type| options.get(*version_type) == Some(version))
+            })
+        };
+
+        let mut skipped = Vec::new();
+        let mut visited = HashSet::new();
+        for key in self.map.keys().sorted().cloned().collect::<Vec<_>>() {
+            if visited.contains(&key) {
+                continue;
+            }
+            let shared = self.shared_keys(&key);
+            visited.extend(shared.iter().cloned());
+            let entries: Vec<&Entry> = shared.iter().filter_map(|key| self.get(key)).collect();
+
+            let mut candidates = allowed_types().flat_map(|version_type| {
+                entries
+                    .iter()
+                    .filter_map(|entry| entry.update_options.as_ref()?.get(version_type))
+                    .sorted()
+                    .rev()
+                    .collect::<Vec<_>>()
+            });
+            let Some(first) = candidates.next() else {
+                continue;
+            };
+            let Some(version) = std::iter::once(first)
+                .chain(candidates)
+                .find(|version| {
+                    entries
+                        .iter()
+                        .all(|entry| offered(entry, version).is_some())
+                })
+                .cloned()
+            else {
+                skipped.extend(
+                    entries
+                        .iter()
+                        .map(|entry| (entry.group.clone(), entry.artifact.clone())),
+                );
+                continue;
+            };
+
+            for key in &shared {
+                let Some(entry) = self.map.get_mut(key) else {
+                    continue;
+                };
+                if let Some(version_type) = offered(entry, &version) {
+                    entry.version_type = version_type;
+                    entry.is_selected = true;
+                }
+            }
+        }
+        skipped.sort();
+        skipped
+    }
+
+    /// The entries sharing a version location with the given one, directly or
+    /// through one another, the given one included.
+    fn shared_keys(&self, key: &DependencyKey) -> Vec<DependencyKey> {
+        let mut shared = vec![key.clone()];
+        let mut index = 0;
+        while let Some(entry) = shared.get(index).and_then(|key| self.get(key)) {
+            for (other_key, other) in &self.map {
+                if !shared.contains(other_key)
+                    && other
+                        .locations
+                        .iter()
+                        .any(|location| entry.locations.contains(location))
+                {
+                    shared.push(other_key.clone());
+                }
+            }
+            index += 1;
+        }
+        shared
+    }
+
+    fn for_each_shared_entry<F>(&mut self, key: &DependencyKey, mut f: F)
     where
         F: FnMut(&mut Entry),
     {
-        if let Some(entry) = self.get(group, artifact) {
+        if let Some(entry) = self.get(key) {
             let locations = entry.locations.clone();
-            for ((_, _), e) in self.map.iter_mut() {
+            for e in self.map.values_mut() {
                 if e.locations.iter().any(|loc| locations.contains(loc)) {
                     f(e);
                 }
@@ -189,37 +504,47 @@ impl EntryMap {
         }
     }
 
-    pub fn toggle_selection(&mut self, group: &Group, artifact: &Artifact) {
-        self.for_each_shared_entry(group, artifact, |e| {
+    pub fn toggle_selection(&mut self, key: &DependencyKey) {
+        self.for_each_shared_entry(key, |e| {
             e.is_selected = !e.is_selected;
         });
     }
 
-    pub(crate) fn deselect(&mut self, group: &Group, artifact: &Artifact) {
-        if let Some(entry) = self.get_mut(group, artifact) {
+    pub(crate) fn deselect(&mut self, key: &DependencyKey) {
+        if let Some(entry) = self.get_mut(key) {
             entry.is_selected = false;
         }
     }
 
-    pub(crate) fn select(&mut self, group: &Group, artifact: &Artifact) {
-        if let Some(entry) = self.get_mut(group, artifact) {
+    pub(crate) fn select(&mut self, key: &DependencyKey) {
+        if let Some(entry) = self.get_mut(key) {
             entry.is_selected = true;
         }
     }
 
-    pub fn next_version_type(&mut self, group: &Group, artifact: &Artifact) {
-        self.change_version_type(group, artifact, |vt| vt.next());
+    /// Switches between migrating to the relocation and updating the version,
+    /// for entries that offer both.
+    pub fn toggle_migrate(&mut self, key: &DependencyKey) {
+        if let Some(entry) = self.get_mut(key) {
+            if entry.relocation.is_some() && entry.update_options.is_some() {
+                entry.migrate = !entry.migrate;
+            }
+        }
+    }
+
+    pub fn next_version_type(&mut self, key: &DependencyKey) {
+        self.change_version_type(key, |vt| vt.next());
     }
 
-    pub fn prev_version_type(&mut self, group: &Group, artifact: &Artifact) {
-        self.change_version_type(group, artifact, |vt| vt.prev());
+    pub fn prev_version_type(&mut self, key: &DependencyKey) {
+        self.change_version_type(key, |vt| vt.prev());
     }
 
-    fn change_version_type<F>(&mut self, group: &Group, artifact: &Artifact, change_fn: F)
+    fn change_version_type<F>(&mut self, key: &DependencyKey, change_fn: F)
     where
         F: Fn(VersionType) -> VersionType,
     {
-        self.for_each_shared_entry(group, artifact, |e| {
+        self.for_each_shared_entry(key, |e| {
             if let Some(update_options) = &e.update_options {
                 loop {
                     e.version_type = change_fn(e.version_type);
@@ -231,3 +556,231 @@ impl EntryMap {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Span;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    impl Entry {
+        /// A library on `current`, offered the `available` versions.
+        pub(crate) fn with_versions(
+            group: &str,
+            artifact: &str,
+            current: &str,
+            available: &[&str],
+        ) -> Entry {
+            let version = Version::new(current);
+            let available: Vec<Version> = available.iter().map(|v| Version::new(v)).collect();
+            Entry {
+                update_options: UpdateOptions::new(&version, &available),
+                available,
+                ..Entry::new(Group::new(group), Artifact::new(artifact), version)
+            }
+        }
+    }
+
+    impl EntryMap {
+        pub(crate) fn from_entries(entries: Vec<Entry>) -> EntryMap {
+            let mut entry_map = EntryMap::new();
+            for entry in entries {
+                entry_map.insert(entry);
+            }
+            entry_map
+        }
+    }
+
+    fn entry_map(entries: &[(&str, &str, &[&str])]) -> EntryMap {
+        EntryMap::from_entries(
+            entries
+                .iter()
+                .map(|(artifact, current, available)| {
+                    Entry::with_versions("dev.zio", artifact, current, available)
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_select_by_policy() {
+        let mut entry_map = entry_map(&[
+            ("zio", "2.0.0", &["2.0.1", "2.1.0", "3.0.0"]),
+            ("zio-json", "0.6.0", &["1.0.0"]),
+            ("zio-schema", "1.0.0", &["1.0.1-RC1", "1.0.1"]),
+        ]);
+
+        entry_map.select_by_policy(&[VersionType::Patch, VersionType::Minor]);
+
+        let selected: Vec<_> = entry_map
+            .selected()
+            .map(|(_, artifact, _, new_version, _)| (artifact.value.clone(), new_version.clone()))
+            .sorted()
+            .collect();
+        assert_eq!(
+            selected,
+            vec![
+                ("zio".to_string(), Version::new("2.1.0")),
+                ("zio-schema".to_string(), Version::new("1.0.1")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_select_shared_entries_by_policy() {
+        let location = |offset| {
+            Location::new(
+                PathBuf::from("project/Versions.scala"),
+                Span::new(offset, offset + 7),
+            )
+        };
+        let shared = |artifact, available, offset| Entry {
+            locations: vec![location(offset)],
+            ..Entry::with_versions("dev.zio", artifact, "2.0.0", available)
+        };
+        let mut entry_map = EntryMap::from_entries(vec![
+            // zio-streams has no 2.1.0 yet, so both stay on the 2.0 line
+            shared("zio", &["2.0.1", "2.1.0"], 10),
+            shared("zio-streams", &["2.0.1"], 10),
+            // nothing in common
+            shared("zio-json", &["2.1.0"], 40),
+            shared("zio-schema", &["2.0.1"], 40),
+        ]);
+
+        let skipped = entry_map.select_by_policy(&[VersionType::Minor, VersionType::Patch]);
+
+        let selected: Vec<_> = entry_map
+            .selected()
+            .map(|(_, artifact, _, new_version, _)| (artifact.value.clone(), new_version.clone()))
+            .sorted()
+            .collect();
+        assert_eq!(
+            selected,
+            vec![
+                ("zio".to_string(), Version::new("2.0.1")),
+                ("zio-streams".to_string(), Version::new("2.0.1")),
+            ]
+        );
+        assert_eq!(
+            skipped,
+            vec![
+                (Group::new("dev.zio"), Artifact::new("zio-json")),
+                (Group::new("dev.zio"), Artifact::new("zio-schema")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_add_release_dates() {
+        let mut entry_map = entry_map(&[("zio", "2.0.0", &["2.0.1", "2.0.2", "2.1.0"])]);
+        entry_map.min_age = Duration::from_secs(7 * 24 * 60 * 60);
+        let (group, artifact) = (Group::new("dev.zio"), Artifact::new("zio"));
+
+        let lookups = entry_map.release_date_lookups();
+        assert_eq!(lookups.len(), 1);
+        assert_eq!(
+            lookups[0].4,
+            vec![Version::new("2.1.0"), Version::new("2.0.2")]
+        );
+
+        let now = ReleaseDate::now();
+        let fresh = ReleaseDate(now.0 - 60 * 60);
+        let old = ReleaseDate(now.0 - 30 * 24 * 60 * 60);
+        entry_map.add_release_dates(&HashMap::from([(
+            (group.clone(), artifact.clone()),
+            HashMap::from([
+                (Version::new("2.1.0"), Some(old)),
+                (Version::new("2.0.2"), Some(fresh)),
+            ]),
+        )]));
+
+        // 2.0.2 is held back, so 2.0.1 is offered instead and needs a date in turn.
+        let entry = entry_map.get(&DependencyKey::new(group, artifact)).unwrap();
+        let options = entry.update_options.as_ref().unwrap();
+        assert_eq!(options.minor, Some(Version::new("2.1.0")));
+        assert_eq!(options.patch, Some(Version::new("2.0.1")));
+        assert_eq!(entry.release_date(&Version::new("2.1.0")), Some(old));
+        assert_eq!(
+            entry_map.release_date_lookups()[0].4,
+            vec![Version::new("2.0.1")]
+        );
+    }
+
+    #[test]
+    fn test_lines_are_offered_their_own_versions() {
+        let (group, artifact) = (Group::new("org.scala-lang"), Artifact::new("scala-library"));
+        let mut entry_map = EntryMap::from_entries(
+            ["2.12.18", "2.13.12"]
+                .map(|version| {
+                    let version = Version::new(version);
+                    Entry {
+                        line: version.line(),
+                        kind: DependencyKind::Toolchain,
+                        ..Entry::new(group.clone(), artifact.clone(), version)
+                    }
+                })
+                .to_vec(),
+        );
+        let versions = ["2.12.18", "2.12.19", "2.13.12", "2.13.14"].map(Version::new);
+        entry_map.add_lookups(&HashMap::from([(
+            (group.clone(), artifact.clone()),
+            VersionLookup {
+                versions: Ok(versions.to_vec()),
+                stale: false,
+            },
+        )]));
+
+        let offered = |line: &str| {
+            let key = DependencyKey::new(group.clone(), artifact.clone()).with_line(line);
+            let options = entry_map.get(&key).unwrap().update_options.clone().unwrap();
+            options.versions().cloned().collect::<Vec<_>>()
+        };
+        assert_eq!(offered("2.12"), vec![Version::new("2.12.19")]);
+        assert_eq!(offered("2.13"), vec![Version::new("2.13.14")]);
+    }
+
+    #[test]
+    fn test_add_relocations() {
+        let mut entry_map = entry_map(&[("zio", "2.0.0", &["2.0.1"]), ("zio-json", "1.0.0", &[])]);
+        let relocation = |artifact: &str| Relocation {
+            group: Group::new("dev.zio.new"),
+            artifact: Artifact::new(artifact),
+            version: Version::new("3.0.0"),
+            message: None,
+        };
+        entry_map.add_relocations(&HashMap::from([
+            (
+                (Group::new("dev.zio"), Artifact::new("zio")),
+                relocation("zio"),
+            ),
+            (
+                (Group::new("dev.zio"), Artifact::new("zio-json")),
+                relocation("zio-json"),
+            ),
+        ]));
+
+        // Entries without updates are offered the migration alone.
+        assert_eq!(entry_map.with_updates().len(), 2);
+        let zio = DependencyKey::new(Group::new("dev.zio"), Artifact::new("zio"));
+        let zio_json = DependencyKey::new(Group::new("dev.zio"), Artifact::new("zio-json"));
+        assert!(!entry_map.get(&zio).unwrap().migrate);
+        assert!(entry_map.get(&zio_json).unwrap().migrate);
+        entry_map.toggle_migrate(&zio_json);
+        assert!(entry_map.get(&zio_json).unwrap().migrate);
+
+        entry_map.toggle_migrate(&zio);
+        entry_map.select(&zio);
+        entry_map.select(&zio_json);
+        assert_eq!(entry_map.selected().count(), 0);
+        let migrations: Vec<_> = entry_map
+            .selected_migrations()
+            .map(|entry| entry.coordinate_update().unwrap().artifact)
+            .sorted()
+            .collect();
+        assert_eq!(
+            migrations,
+            vec![Artifact::new("zio"), Artifact::new("zio-json")]
+        );
+    }
+}
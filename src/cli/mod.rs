@@ -1,54 +1,289 @@
+mod args;
+pub mod check;
 mod entry_map;
+pub mod report;
 
 use altar::*;
-pub use entry_map::EntryMap;
+use anyhow::Result;
+pub use args::{Args, OutputFormat};
+pub use entry_map::{Entry, EntryMap};
 
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, path::Path, sync::Arc, time::Duration};
+
+use reqwest::Client;
 
 use crate::{
+    config::Config,
     dependency_resolver::{self},
     model::{
         update_options::{UpdateOptions, VersionType},
-        Artifact, Group, Version,
+        Artifact, BuildTarget, Group, ReleaseDate, Relocation, Version,
+    },
+    package_search::{
+        artifact_candidates, maven::MavenPackageSearch, PackageSearchExt, ReleaseDates,
+        VersionLookup,
     },
-    package_search::{maven::MavenPackageSearch, PackageSearchExt},
+    release_notes::{ReleaseNote, ReleaseNotesClient},
 };
 
+/// Collects the project's dependencies and resolves their available versions
+/// and relocations, without going through the interactive UI.
+/// Updates published less than `min_age` ago are held back.
+pub async fn load_entry_map(
+    project_path: &Path,
+    maven_package_search: &MavenPackageSearch,
+    min_age: Duration,
+) -> Result<EntryMap> {
+    let dependencies = dependency_resolver::collect_dependencies(project_path)?;
+    let mut entry_map = EntryMap::from_dependency_map(&dependencies);
+    entry_map.min_age = min_age;
+
+    let maven_package_search = maven_package_search.with_repositories(dependencies.repositories());
+    let build_target = dependencies.build_target();
+    let lookups = maven_package_search
+        .lookup_multiple_versions(entry_map.lookups(), &build_target)
+        .await;
+    entry_map.add_lookups(&lookups);
+
+    let relocations = maven_package_search
+        .lookup_relocations(entry_map.relocation_lookups(), &build_target)
+        .await;
+    entry_map.add_relocations(&relocations);
+
+    if !min_age.is_zero() {
+        loop {
+            let lookups = entry_map.release_date_lookups();
+            if lookups.is_empty() {
+                break;
+            }
+            let dates = maven_package_search
+                .lookup_release_dates(lookups, &build_target)
+                .await;
+            entry_map.add_release_dates(&dates);
+        }
+    }
+
+    Ok(entry_map)
+}
+
 pub struct SupApp {
     pub entry_map: EntryMap,
     maven_package_search: Arc<MavenPackageSearch>,
+    release_notes_client: ReleaseNotesClient,
+    build_target: BuildTarget,
+    min_age: Duration,
     selected_index: u16,
     show_group: bool,
+    show_notes: bool,
+    /// The release notes of each update looked at so far.
+    release_notes: HashMap<(Group, Artifact, Version), ReleaseNotesState>,
+    notes_scroll: u16,
+    looking_up_relocations: bool,
+    /// Whether release dates are being looked up, which must finish before
+    /// updating when a min age holds back recent versions.
+    looking_up_release_dates: bool,
     pub decided_to_update: bool,
 }
 
+/// The release notes between an entry's version and one of its updates.
+#[derive(Debug, Clone)]
+pub enum ReleaseNotesState {
+    Loading,
+    Loaded(Vec<ReleaseNote>),
+    Failed(String),
+}
+
+/// The size of the release notes pane, in columns and lines.
+const NOTES_WIDTH: usize = 72;
+const NOTES_HEIGHT: usize = 20;
+
 impl Default for SupApp {
     fn default() -> Self {
         Self {
             entry_map: EntryMap::new(),
             maven_package_search: Arc::new(MavenPackageSearch::new()),
+            release_notes_client: ReleaseNotesClient::new(
+                Client::new(),
+                &Config::default().github_api_url,
+            ),
+            build_target: BuildTarget::default(),
+            min_age: Duration::ZERO,
             selected_index: 0,
             show_group: false,
+            show_notes: false,
+            release_notes: HashMap::new(),
+            notes_scroll: 0,
+            looking_up_relocations: false,
+            looking_up_release_dates: false,
             decided_to_update: false,
         }
     }
 }
 
 impl SupApp {
+    pub fn new(maven_package_search: MavenPackageSearch, min_age: Duration) -> Self {
+        Self {
+            maven_package_search: Arc::new(maven_package_search),
+            min_age,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_release_notes(mut self, release_notes_client: ReleaseNotesClient) -> Self {
+        self.release_notes_client = release_notes_client;
+        self
+    }
+
+    fn toggle_show_notes(&mut self) {
+        self.show_notes = !self.show_notes;
+        self.notes_scroll = 0;
+    }
+
+    /// The selected entry and the update it is set to.
+    fn selected_update(&self) -> Option<(Group, Artifact, entry_map::Entry, Version)> {
+        let (group, artifact, entry) = self
+            .entry_map
+            .with_updates()
+            .get(self.selected_index as usize)?
+            .clone();
+        let version = entry.current_update_version()?.clone();
+        Some((group, artifact, entry, version))
+    }
+
+    /// Fetches the release notes of the selected update in the background, unless
+    /// the notes pane is hidden or they were already fetched.
+    fn request_release_notes(&mut self, sender: &tokio::sync::mpsc::UnboundedSender<Message>) {
+        if !self.show_notes {
+            return;
+        }
+        let Some((group, artifact, entry, version)) = self.selected_update() else {
+            return;
+        };
+        let key = (group.clone(), artifact.clone(), version.clone());
+        if self.release_notes.contains_key(&key) {
+            return;
+        }
+        self.release_notes
+            .insert(key.clone(), ReleaseNotesState::Loading);
+
+        let candidates =
+            artifact_candidates(&artifact, entry.kind, entry.cross, &self.build_target);
+        let search = self.maven_package_search.clone();
+        let client = self.release_notes_client.clone();
+        let sender = sender.clone();
+        tokio::spawn(async move {
+            let links = search
+                .get_project_links(&group, &candidates, &version)
+                .await;
+            let notes = match links.map(|links| links.github_repository()) {
+                Ok(Some(repository_url)) => client
+                    .fetch(&repository_url, &entry.version, &version)
+                    .await
+                    .map_err(|err| format!("{:#}", err)),
+                Ok(None) => Err("The POM names no GitHub repository".to_string()),
+                Err(err) => Err(format!("{:#}", err)),
+            };
+            let _ = sender.send(Message::ReleaseNotesRetrieved(key, notes));
+        });
+    }
+
+    /// Opens the selected update's GitHub release in the browser, or failing that
+    /// its source repository, homepage, or directory in the Maven repository.
+    fn open_selected_update(&self) {
+        let Some((group, artifact, entry, version)) = self.selected_update() else {
+            return;
+        };
+        let candidates =
+            artifact_candidates(&artifact, entry.kind, entry.cross, &self.build_target);
+        let search = self.maven_package_search.clone();
+        let client = self.release_notes_client.clone();
+        tokio::spawn(async move {
+            let Ok(links) = search
+                .get_project_links(&group, &candidates, &version)
+                .await
+            else {
+                return;
+            };
+            let url = match links.github_repository() {
+                Some(repository) => {
+                    let tag = client
+                        .release_tag(&repository, &version, links.tag.as_deref())
+                        .await
+                        .unwrap_or(links.tag);
+                    match tag {
+                        Some(tag) => format!("{}/releases/tag/{}", repository, tag),
+                        None => format!("{}/releases", repository),
+                    }
+                }
+                None => match links.best_url() {
+                    Some(url) => url.clone(),
+                    None => return,
+                },
+            };
+            let _ = webbrowser::open(&url);
+        });
+    }
+
+    /// Looks up when the offered updates were published, in the background.
+    fn lookup_release_dates(&mut self, sender: &tokio::sync::mpsc::UnboundedSender<Message>) {
+        let lookups = self.entry_map.release_date_lookups();
+        if lookups.is_empty() {
+            return;
+        }
+        self.looking_up_release_dates = true;
+        let maven_package_search = self.maven_package_search.clone();
+        let build_target = self.build_target.clone();
+        let sender = sender.clone();
+        tokio::spawn(async move {
+            let dates = maven_package_search
+                .lookup_release_dates(lookups, &build_target)
+                .await;
+            let _ = sender.send(Message::ReleaseDatesRetrieved(dates));
+        });
+    }
+
+    /// Looks up which dependencies have moved to new coordinates, in the background.
+    fn lookup_relocations(&mut self, sender: &tokio::sync::mpsc::UnboundedSender<Message>) {
+        let lookups = self.entry_map.relocation_lookups();
+        if lookups.is_empty() {
+            return;
+        }
+        self.looking_up_relocations = true;
+        let maven_package_search = self.maven_package_search.clone();
+        let build_target = self.build_target.clone();
+        let sender = sender.clone();
+        tokio::spawn(async move {
+            let relocations = maven_package_search
+                .lookup_relocations(lookups, &build_target)
+                .await;
+            let _ = sender.send(Message::RelocationsRetrieved(relocations));
+        });
+    }
+
+    fn toggle_migrate(&mut self) {
+        if let Some((_, _, entry)) = self
+            .entry_map
+            .with_updates()
+            .get(self.selected_index as usize)
+        {
+            self.entry_map.toggle_migrate(&entry.key());
+        }
+    }
+
     fn toggle_show_group(&mut self) {
         self.show_group = !self.show_group;
     }
 
     fn change_version(&mut self, direction: i8) {
-        if let Some((group, artifact, _)) = self
+        if let Some((_, _, entry)) = self
             .entry_map
             .with_updates()
             .get(self.selected_index as usize)
         {
             if direction > 0 {
-                self.entry_map.next_version_type(group, artifact);
+                self.entry_map.next_version_type(&entry.key());
             } else {
-                self.entry_map.prev_version_type(group, artifact);
+                self.entry_map.prev_version_type(&entry.key());
             }
         }
     }
@@ -62,12 +297,12 @@ impl SupApp {
     }
 
     fn toggle_selection(&mut self) {
-        if let Some((group, artifact, _)) = self
+        if let Some((_, _, entry)) = self
             .entry_map
             .with_updates()
             .get(self.selected_index as usize)
         {
-            self.entry_map.toggle_selection(group, artifact);
+            self.entry_map.toggle_selection(&entry.key());
         }
     }
 
@@ -78,11 +313,11 @@ impl SupApp {
             .iter()
             .all(|(_, _, entry)| entry.is_selected);
 
-        for (group, artifact, _) in self.entry_map.with_updates() {
+        for (_, _, entry) in self.entry_map.with_updates() {
             if all_selected {
-                self.entry_map.deselect(&group, &artifact);
+                self.entry_map.deselect(&entry.key());
             } else {
-                self.entry_map.select(&group, &artifact);
+                self.entry_map.select(&entry.key());
             }
         }
     }
@@ -93,11 +328,16 @@ fn render_update_options(
     is_current: bool,
     update_options: &UpdateOptions,
     version_type: &VersionType,
+    migrate: bool,
+    release_dates: &HashMap<Version, Option<ReleaseDate>>,
 ) -> impl View {
     let render_version_option = |option: Option<Version>, target_type: VersionType| {
-        let is_selected_type = *version_type == target_type;
+        let is_selected_type = !migrate && *version_type == target_type;
         option
-            .map(|v| text(format!("{}", v)))
+            .map(|v| match release_dates.get(&v).copied().flatten() {
+                Some(date) => text(format!("{} ({})", v, date)),
+                None => text(format!("{}", v)),
+            })
             .underline_when(is_selected && is_selected_type)
             .dim_when(!is_selected_type)
             .bold_when(is_current && is_selected_type)
@@ -111,11 +351,31 @@ fn render_update_options(
             .magenta(),
         text(format!("{}", version_type))
             .yellow()
-            .visible(is_current),
+            .visible(is_current && !migrate),
     ))
     .green()
 }
 
+/// The new coordinates a dependency can migrate to, e.g. `⇢ com.github.sbt:sbt-git 2.1.0`.
+fn render_relocation(
+    is_selected: bool,
+    is_current: bool,
+    relocation: &Relocation,
+    migrate: bool,
+) -> impl View {
+    hstack((
+        text(format!(
+            "⇢ {}:{} {}",
+            relocation.group, relocation.artifact, relocation.version
+        ))
+        .underline_when(is_selected && migrate)
+        .dim_when(!migrate)
+        .bold_when(is_current && migrate),
+        text("Migrate").yellow().visible(is_current && migrate),
+    ))
+    .cyan()
+}
+
 fn render_dependency(
     show_group: bool,
     is_current: bool,
@@ -157,15 +417,43 @@ fn render_dependency(
         ))
         .dim(),
         text("→").dim(),
-        render_update_options(
-            entry.is_selected,
-            is_current,
-            entry.update_options.as_ref().unwrap(),
-            &entry.version_type,
-        ),
+        entry.update_options.as_ref().map(|update_options| {
+            render_update_options(
+                entry.is_selected,
+                is_current,
+                update_options,
+                &entry.version_type,
+                entry.migrate,
+                &entry.release_dates,
+            )
+        }),
+        entry.relocation.as_ref().map(|relocation| {
+            render_relocation(entry.is_selected, is_current, relocation, entry.migrate)
+        }),
+        entry.kind.label().map(|label| text(label).cyan().dim()),
+        text(usage(entry))
+            .dim()
+            .visible(!entry.configurations.is_empty() || !entry.classifiers.is_empty()),
+        text("(stale)").yellow().dim().visible(entry.is_stale),
     ))
 }
 
+/// How the dependency is consumed, e.g. `% Test classifier tests`.
+fn usage(entry: &entry_map::Entry) -> String {
+    let configurations = entry
+        .configurations
+        .iter()
+        .map(|configuration| format!("% {}", configuration));
+    let classifiers = entry
+        .classifiers
+        .iter()
+        .map(|classifier| format!("classifier {}", classifier));
+    configurations
+        .chain(classifiers)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 fn render_dependencies(
     dependencies: &[(Group, Artifact, entry_map::Entry)],
     selected_index: u16,
@@ -186,7 +474,7 @@ fn render_dependencies(
         dependencies
             .iter()
             .enumerate()
-            .map(|(index, (group, artifact, entry))| {
+            .map(|(index, (_, _, entry))| {
                 render_dependency(
                     show_group,
                     selected_index == index as u16,
@@ -195,26 +483,133 @@ fn render_dependencies(
                     artifact_width,
                     version_width,
                 )
-                .id((group, artifact))
+                .id(entry.key())
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// The dependencies whose versions could not be looked up, e.g. because the
+/// repository kept rate limiting us.
+pub fn render_lookup_failures(failures: &[(Group, Artifact, entry_map::Entry)]) -> impl View {
+    let plural = if failures.len() == 1 {
+        "dependency"
+    } else {
+        "dependencies"
+    };
+    let coordinates: Vec<String> = failures
+        .iter()
+        .map(|(group, artifact, _)| format!("{}:{}", group, artifact))
+        .collect();
+    let width = coordinates.iter().map(|c| c.len()).max().unwrap_or(0);
+
+    vstack((
+        text(format!("  Could not check {} {}:", failures.len(), plural)).red(),
+        vstack(
+            failures
+                .iter()
+                .zip(coordinates)
+                .map(|((_, _, entry), coordinate)| {
+                    hstack((
+                        text(format!("  {:<width$}", coordinate)).red(),
+                        text(entry.lookup_error.clone().unwrap_or_default()).dim(),
+                    ))
+                    .id(entry.key())
+                })
+                .collect::<Vec<_>>(),
+        ),
+    ))
+}
+
+/// Wraps a line of text at word boundaries to fit the given width.
+fn wrap(line: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![String::new()];
+    for word in line.split_whitespace() {
+        let current = lines.last_mut().unwrap();
+        if !current.is_empty() && current.chars().count() + 1 + word.chars().count() > width {
+            lines.push(String::new());
+        }
+        let current = lines.last_mut().unwrap();
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    lines
+}
+
+/// The lines of the notes pane, as text and whether it is a heading.
+fn release_notes_lines(state: Option<&ReleaseNotesState>) -> Vec<(String, bool)> {
+    match state {
+        None | Some(ReleaseNotesState::Loading) => vec![("Loading...".to_string(), false)],
+        Some(ReleaseNotesState::Failed(err)) => wrap(err, NOTES_WIDTH)
+            .into_iter()
+            .map(|line| (line, false))
+            .collect(),
+        Some(ReleaseNotesState::Loaded(notes)) if notes.is_empty() => {
+            vec![("No release notes found.".to_string(), false)]
+        }
+        Some(ReleaseNotesState::Loaded(notes)) => notes
+            .iter()
+            .flat_map(|note| {
+                let heading = format!("{} · {}", note.version, note.title);
+                let body = note.body.lines().flat_map(|line| wrap(line, NOTES_WIDTH));
+                std::iter::once((heading, true))
+                    .chain(body.map(|line| (line, false)))
+                    .chain(std::iter::once((String::new(), false)))
+            })
+            .collect(),
+    }
+}
+
+fn render_release_notes(
+    title: String,
+    state: Option<&ReleaseNotesState>,
+    scroll: u16,
+) -> impl View {
+    let lines = release_notes_lines(state);
+    let scroll = (scroll as usize).min(lines.len().saturating_sub(1));
+    vstack(
+        lines
+            .into_iter()
+            .skip(scroll)
+            .take(NOTES_HEIGHT)
+            .enumerate()
+            .map(|(index, (line, is_heading))| {
+                text(format!("{:<width$}", line, width = NOTES_WIDTH))
+                    .bold_when(is_heading)
+                    .color(if is_heading {
+                        Color::Green
+                    } else {
+                        Color::Reset
+                    })
+                    .id(index)
             })
             .collect::<Vec<_>>(),
     )
+    .padding_h(1)
+    .border()
+    .title(title)
 }
 
 fn render_command(key: &str, label: &str) -> impl View {
     hstack((text(key), text(label).dim()))
 }
 
-fn render_commands(show_group: bool) -> impl View {
+fn render_commands(show_group: bool, show_notes: bool, can_migrate: bool) -> impl View {
     let show_groups_text = if show_group {
         "hide groups"
     } else {
         "show groups"
     };
+    let show_notes_text = if show_notes { "hide notes" } else { "notes" };
     hstack((
         render_command("space", "toggle"),
         render_command("a", "toggle all"),
         render_command("g", show_groups_text),
+        render_command("n", show_notes_text),
+        render_command("J/K", "scroll notes").visible(show_notes),
+        render_command("m", "migrate").visible(can_migrate),
         render_command("q", "quit"),
     ))
     .spacing(2)
@@ -224,7 +619,13 @@ fn render_commands(show_group: bool) -> impl View {
 
 #[derive(Debug)]
 pub enum Message {
-    VersionsRetrieved(HashMap<(Group, Artifact), Vec<Version>>),
+    VersionsRetrieved(HashMap<(Group, Artifact), VersionLookup>),
+    ReleaseDatesRetrieved(ReleaseDates),
+    RelocationsRetrieved(HashMap<(Group, Artifact), Relocation>),
+    ReleaseNotesRetrieved(
+        (Group, Artifact, Version),
+        std::result::Result<Vec<ReleaseNote>, String>,
+    ),
 }
 
 impl AsyncTerminalApp for SupApp {
@@ -236,10 +637,11 @@ impl AsyncTerminalApp for SupApp {
 
     fn render(&self) -> impl View {
         let dependencies = self.entry_map.with_updates();
+        let failures = self.entry_map.with_errors();
         let selected_index = self.selected_index;
         let show_group = self.show_group;
 
-        if dependencies.is_empty() {
+        if dependencies.is_empty() && failures.is_empty() {
             hstack((
                 text("  Δ GIVEN UPDATE").green(),
                 text("LOADING...").green().dim(),
@@ -248,12 +650,31 @@ impl AsyncTerminalApp for SupApp {
             .as_any()
             .id("Loading")
         } else {
+            let notes = self.selected_update().filter(|_| self.show_notes).map(
+                |(group, artifact, entry, version)| {
+                    let title = format!(" {} {} → {} ", artifact, entry.version, version);
+                    let state = self.release_notes.get(&(group, artifact, version));
+                    render_release_notes(title, state, self.notes_scroll)
+                },
+            );
+            // Only entries offering both a version update and a migration can switch.
+            let can_migrate =
+                dependencies
+                    .get(selected_index as usize)
+                    .is_some_and(|(_, _, entry)| {
+                        entry.relocation.is_some() && entry.update_options.is_some()
+                    });
             vstack((
                 text("  Δ GIVEN UPDATE").green(),
                 "",
-                render_dependencies(&dependencies, selected_index, show_group),
+                hstack((
+                    render_dependencies(&dependencies, selected_index, show_group),
+                    notes,
+                ))
+                .spacing(2),
                 "",
-                render_commands(self.show_group),
+                vstack((render_lookup_failures(&failures), "")).visible(!failures.is_empty()),
+                render_commands(self.show_group, self.show_notes, can_migrate),
             ))
             .padding_v(1)
             .as_any()
@@ -264,14 +685,16 @@ impl AsyncTerminalApp for SupApp {
     fn update(
         &mut self,
         event: Event<Self::Message>,
-        _sender: &tokio::sync::mpsc::UnboundedSender<Self::Message>,
+        sender: &tokio::sync::mpsc::UnboundedSender<Self::Message>,
     ) -> bool {
         match event {
             Event::Key(key) => match key.code {
                 KeyCode::Char('q') => return false,
+                _ if self.entry_map.with_updates().is_empty() => (),
                 KeyCode::Char('j') | KeyCode::Down => {
                     self.selected_index =
                         (self.selected_index + 1) % self.entry_map.with_updates().len() as u16;
+                    self.notes_scroll = 0;
                 }
                 KeyCode::Char('k') | KeyCode::Up => {
                     self.selected_index = if self.selected_index == 0 {
@@ -279,12 +702,24 @@ impl AsyncTerminalApp for SupApp {
                     } else {
                         self.selected_index - 1
                     };
+                    self.notes_scroll = 0;
                 }
                 KeyCode::Right | KeyCode::Tab => {
                     self.next_version();
+                    self.notes_scroll = 0;
                 }
                 KeyCode::Left | KeyCode::BackTab => {
                     self.prev_version();
+                    self.notes_scroll = 0;
+                }
+                KeyCode::Char('n') => {
+                    self.toggle_show_notes();
+                }
+                KeyCode::Char('J') => {
+                    self.notes_scroll = self.notes_scroll.saturating_add(1);
+                }
+                KeyCode::Char('K') => {
+                    self.notes_scroll = self.notes_scroll.saturating_sub(1);
                 }
                 KeyCode::Char(' ') => {
                     self.toggle_selection();
@@ -296,68 +731,87 @@ impl AsyncTerminalApp for SupApp {
                     self.toggle_show_group();
                 }
                 KeyCode::Char('o') => {
-                    let (group, artifact, entry) = {
-                        let entry_map = self.entry_map.with_updates();
-                        entry_map.get(self.selected_index as usize).unwrap().clone()
-                    };
-
-                    if let Some(version) = entry.current_update_version() {
-                        let group = group.clone();
-                        let artifact = artifact.clone();
-                        let version = version.clone();
-                        tokio::spawn(async move {
-                            let search = MavenPackageSearch::new();
-                            if let Ok(Some(github_url)) =
-                                search.get_github_repo(&group, &artifact, &version).await
-                            {
-                                let release_url =
-                                    format!("{}/releases/tag/v{}", github_url, version);
-                                let _ = webbrowser::open(&release_url);
-                            }
-                        });
-                    }
+                    self.open_selected_update();
                 }
+                KeyCode::Char('m') => {
+                    self.toggle_migrate();
+                    self.notes_scroll = 0;
+                }
+                // the offered updates may still be too recent for the min age
+                KeyCode::Enter if self.looking_up_release_dates && !self.min_age.is_zero() => (),
                 KeyCode::Enter => {
                     self.decided_to_update = true;
                     return false;
                 }
                 _ => (),
             },
-            Event::Message(Message::VersionsRetrieved(versions_map)) => {
-                self.entry_map.add_versions(&versions_map);
+            Event::Message(Message::ReleaseNotesRetrieved(key, notes)) => {
+                let state = match notes {
+                    Ok(notes) => ReleaseNotesState::Loaded(notes),
+                    Err(err) => ReleaseNotesState::Failed(err),
+                };
+                self.release_notes.insert(key, state);
+            }
+            Event::Message(Message::VersionsRetrieved(lookups)) => {
+                self.entry_map.add_lookups(&lookups);
+
+                self.lookup_relocations(sender);
+                if self.entry_map.with_updates().is_empty() && !self.looking_up_relocations {
+                    return false;
+                }
+                self.lookup_release_dates(sender);
+            }
+            Event::Message(Message::RelocationsRetrieved(relocations)) => {
+                self.looking_up_relocations = false;
+                self.entry_map.add_relocations(&relocations);
 
                 if self.entry_map.with_updates().is_empty() {
                     return false;
                 }
             }
+            Event::Message(Message::ReleaseDatesRetrieved(dates)) => {
+                self.looking_up_release_dates = false;
+                self.entry_map.add_release_dates(&dates);
+
+                // Held back updates may have emptied the list, or shortened it.
+                let len = self.entry_map.with_updates().len() as u16;
+                if len == 0 {
+                    if !self.looking_up_relocations {
+                        return false;
+                    }
+                } else {
+                    self.selected_index = self.selected_index.min(len - 1);
+                }
+                self.lookup_release_dates(sender);
+            }
         }
+        self.request_release_notes(sender);
         true
     }
 
     fn init(&mut self, sender: &tokio::sync::mpsc::UnboundedSender<Self::Message>) {
         let current_dir = std::env::current_dir().unwrap();
-        let dependencies = dependency_resolver::collect_sbt_dependencies(&current_dir).unwrap();
+        let dependencies = dependency_resolver::collect_dependencies(&current_dir).unwrap();
         self.entry_map = EntryMap::from_dependency_map(&dependencies);
+        self.entry_map.min_age = self.min_age;
+        self.maven_package_search = Arc::new(
+            self.maven_package_search
+                .with_repositories(dependencies.repositories()),
+        );
 
-        let all_groups_and_artifacts = self.entry_map.groups_and_artifacts();
+        let lookups = self.entry_map.lookups();
         let maven_package_search = self.maven_package_search.clone();
         let sender_clone = sender.clone();
 
-        let maybe_scala_version = dependencies
-            .iter()
-            .find(|((group, artifact), _)| {
-                group.value == "org.scala-lang"
-                    && (artifact.value == "scala-library" || artifact.value == "scala3-library_3")
-            })
-            .map(|((_, _), version)| (version.version.clone()));
+        let build_target = dependencies.build_target();
+        self.build_target = build_target.clone();
 
         tokio::spawn(async move {
-            let versions_map = maven_package_search
-                .get_multiple_versions(all_groups_and_artifacts, maybe_scala_version)
-                .await
-                .unwrap_or_default();
+            let lookups = maven_package_search
+                .lookup_multiple_versions(lookups, &build_target)
+                .await;
 
-            let _ = sender_clone.send(Message::VersionsRetrieved(versions_map));
+            let _ = sender_clone.send(Message::VersionsRetrieved(lookups));
         });
     }
 }
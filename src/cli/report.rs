@@ -0,0 +1,269 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use super::{entry_map::Entry, EntryMap};
+use crate::{
+    dependency_resolver::Location,
+    model::{
+        update_options::{UpdateOptions, VersionType},
+        Version,
+    },
+    parser::span::LineColumn,
+};
+
+/// Bumped whenever a field is renamed, removed or changes meaning.
+/// Adding new fields does not change the schema version.
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct Report {
+    pub schema_version: u32,
+    pub dependencies: Vec<DependencyReport>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct DependencyReport {
+    pub group: String,
+    pub artifact: String,
+    pub current_version: String,
+    pub updates: UpdatesReport,
+    pub outdated: bool,
+    pub locations: Vec<LocationReport>,
+    pub error: Option<String>,
+    /// The new coordinates the artifact has moved to.
+    pub relocation: Option<RelocationReport>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct RelocationReport {
+    pub group: String,
+    pub artifact: String,
+    pub version: String,
+}
+
+#[derive(Serialize, Debug, PartialEq, Default)]
+pub struct UpdatesReport {
+    pub major: Option<String>,
+    pub minor: Option<String>,
+    pub patch: Option<String>,
+    pub pre_release: Option<String>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct LocationReport {
+    pub path: PathBuf,
+    pub start: PositionReport,
+    pub end: PositionReport,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct PositionReport {
+    pub line: usize,
+    pub column: usize,
+    pub offset: usize,
+}
+
+/// A single line of NDJSON output: one dependency, tagged with the schema version.
+#[derive(Serialize, Debug, PartialEq)]
+struct ReportLine<'a> {
+    schema_version: u32,
+    #[serde(flatten)]
+    dependency: &'a DependencyReport,
+}
+
+impl From<&UpdateOptions> for UpdatesReport {
+    fn from(options: &UpdateOptions) -> Self {
+        let to_string = |v: &Version| v.to_string();
+        UpdatesReport {
+            major: options.major.as_ref().map(to_string),
+            minor: options.minor.as_ref().map(to_string),
+            patch: options.patch.as_ref().map(to_string),
+            pre_release: options.pre_release.as_ref().map(to_string),
+        }
+    }
+}
+
+impl Report {
+    /// Builds a report of every entry. Paths are made relative to `project_path`
+    /// when possible.
+    pub fn new(project_path: &Path, entry_map: &EntryMap, fail_on: &[VersionType]) -> Result<Self> {
+        let mut sources = HashMap::new();
+        let dependencies = entry_map
+            .sorted()
+            .iter()
+            .map(|(_, _, entry)| DependencyReport::new(project_path, entry, fail_on, &mut sources))
+            .collect::<Result<_>>()?;
+
+        Ok(Report {
+            schema_version: SCHEMA_VERSION,
+            dependencies,
+        })
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).context("Failed to serialize report")
+    }
+
+    pub fn to_ndjson(&self) -> Result<String> {
+        let mut output = String::new();
+        for dependency in &self.dependencies {
+            let line = ReportLine {
+                schema_version: self.schema_version,
+                dependency,
+            };
+            output.push_str(&serde_json::to_string(&line).context("Failed to serialize report")?);
+            output.push('\n');
+        }
+        Ok(output)
+    }
+}
+
+impl DependencyReport {
+    fn new(
+        project_path: &Path,
+        entry: &Entry,
+        fail_on: &[VersionType],
+        sources: &mut HashMap<PathBuf, String>,
+    ) -> Result<Self> {
+        let locations = entry
+            .locations
+            .iter()
+            .map(|location| LocationReport::new(project_path, location, sources))
+            .collect::<Result<_>>()?;
+
+        Ok(DependencyReport {
+            group: entry.group.value.clone(),
+            artifact: entry.artifact.value.clone(),
+            current_version: entry.version.to_string(),
+            updates: entry
+                .update_options
+                .as_ref()
+                .map(UpdatesReport::from)
+                .unwrap_or_default(),
+            outdated: entry
+                .update_options
+                .as_ref()
+                .is_some_and(|options| options.has_any(fail_on)),
+            locations,
+            error: entry.lookup_error.clone(),
+            relocation: entry
+                .relocation
+                .as_ref()
+                .map(|relocation| RelocationReport {
+                    group: relocation.group.value.clone(),
+                    artifact: relocation.artifact.value.clone(),
+                    version: relocation.version.to_string(),
+                }),
+        })
+    }
+}
+
+impl LocationReport {
+    fn new(
+        project_path: &Path,
+        location: &Location,
+        sources: &mut HashMap<PathBuf, String>,
+    ) -> Result<Self> {
+        if !sources.contains_key(&location.path) {
+            let code = fs::read_to_string(&location.path)
+                .with_context(|| format!("Failed to read {}", location.path.display()))?;
+            sources.insert(location.path.clone(), code);
+        }
+        let code = &sources[&location.path];
+
+        let position = |offset: usize| {
+            let LineColumn { line, column } = LineColumn::from_offset(code, offset);
+            PositionReport {
+                line,
+                column,
+                offset,
+            }
+        };
+
+        Ok(LocationReport {
+            path: location
+                .path
+                .strip_prefix(project_path)
+                .unwrap_or(&location.path)
+                .to_path_buf(),
+            start: position(location.span.start),
+            end: position(location.span.end),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        model::{Artifact, Group},
+        parser::Span,
+    };
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_report_serialization() -> Result<()> {
+        let dir = tempdir()?;
+        let build_sbt_path = dir.path().join("build.sbt");
+        fs::write(
+            &build_sbt_path,
+            "libraryDependencies ++= Seq(\n  \"dev.zio\" %% \"zio\" % \"2.0.0\"\n)\n",
+        )?;
+
+        let current = Version::new("2.0.0");
+        let mut entry_map = EntryMap::new();
+        for (artifact, update_options, lookup_error) in [
+            (
+                "zio",
+                UpdateOptions::new(&current, &[Version::new("2.0.1")]),
+                None,
+            ),
+            ("zio-json", None, Some("Request failed".to_string())),
+        ] {
+            entry_map.insert(Entry {
+                locations: vec![Location::new(build_sbt_path.clone(), Span::new(52, 59))],
+                update_options,
+                version_type: VersionType::Patch,
+                lookup_error,
+                available: vec![Version::new("2.0.1")],
+                ..Entry::new(
+                    Group::new("dev.zio"),
+                    Artifact::new(artifact),
+                    current.clone(),
+                )
+            });
+        }
+
+        let report = Report::new(dir.path(), &entry_map, &[VersionType::Patch])?;
+        assert_eq!(
+            report.to_ndjson()?,
+            concat!(
+                r#"{"schema_version":1,"group":"dev.zio","artifact":"zio","#,
+                r#""current_version":"2.0.0","updates":{"major":null,"minor":null,"#,
+                r#""patch":"2.0.1","pre_release":null},"outdated":true,"#,
+                r#""locations":[{"path":"build.sbt","#,
+                r#""start":{"line":2,"column":24,"offset":52},"#,
+                r#""end":{"line":2,"column":31,"offset":59}}],"error":null,"#,
+                r#""relocation":null}"#,
+                "\n",
+                r#"{"schema_version":1,"group":"dev.zio","artifact":"zio-json","#,
+                r#""current_version":"2.0.0","updates":{"major":null,"minor":null,"#,
+                r#""patch":null,"pre_release":null},"outdated":false,"#,
+                r#""locations":[{"path":"build.sbt","#,
+                r#""start":{"line":2,"column":24,"offset":52},"#,
+                r#""end":{"line":2,"column":31,"offset":59}}],"error":"Request failed","#,
+                r#""relocation":null}"#,
+                "\n",
+            )
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,155 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::model::{Migration, Repository};
+
+/// The user's configuration, read from `$XDG_CONFIG_HOME/given/config.toml`
+/// (or `~/.config/given/config.toml`). The `GIVEN_CONFIG` environment variable
+/// points at a different file.
+///
+/// ```toml
+/// cache-ttl-hours = 24
+/// max-concurrent-requests = 8
+/// max-retries = 3
+/// ca-certificates = ["/etc/ssl/certs/corporate-ca.pem"]
+/// min-age-days = 3
+/// github-api-url = "https://api.github.com"
+///
+/// [[repositories]]
+/// name = "Internal"
+/// url = "https://artifactory.example.com/artifactory/maven"
+///
+/// [[migrations]]
+/// old-group = "com.example.legacy"
+/// new-group = "com.example"
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    /// Repositories queried after Maven Central, ahead of the build's own resolvers.
+    pub repositories: Vec<Repository>,
+    /// How long fetched version lists are reused before being fetched again.
+    pub cache_ttl_hours: u64,
+    /// How many requests may be in flight at once, across every repository.
+    pub max_concurrent_requests: usize,
+    /// How often rate-limited or failed requests are retried.
+    pub max_retries: u32,
+    /// PEM files with additional root certificates to trust, e.g. a corporate CA.
+    pub ca_certificates: Vec<PathBuf>,
+    /// Updates published fewer than this many days ago are held back.
+    pub min_age_days: u64,
+    /// Where release notes are fetched from, e.g. a GitHub Enterprise instance's API.
+    pub github_api_url: String,
+    /// Artifacts known to have moved, on top of the bundled table.
+    pub migrations: Vec<Migration>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            repositories: Vec::new(),
+            cache_ttl_hours: 24,
+            max_concurrent_requests: 8,
+            max_retries: 3,
+            ca_certificates: Vec::new(),
+            min_age_days: 0,
+            github_api_url: "https://api.github.com".to_string(),
+            migrations: Vec::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Loads the user's configuration, or the defaults if there is no config file.
+    pub fn load() -> Result<Config> {
+        match Self::path() {
+            Some(path) if path.exists() => Self::load_from(&path),
+            _ => Ok(Config::default()),
+        }
+    }
+
+    pub fn load_from(path: &Path) -> Result<Config> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&text).with_context(|| format!("Invalid config file {}", path.display()))
+    }
+
+    pub fn parse(text: &str) -> Result<Config> {
+        let mut config: Config = toml::from_str(text)?;
+        config.repositories = config
+            .repositories
+            .iter()
+            .map(|repository| Repository::new(&repository.name, &repository.url))
+            .collect();
+        Ok(config)
+    }
+
+    /// The user's migrations, followed by the bundled ones.
+    pub fn migrations(&self) -> Vec<Migration> {
+        let mut migrations = self.migrations.clone();
+        migrations.extend(Migration::bundled());
+        migrations
+    }
+
+    pub fn path() -> Option<PathBuf> {
+        if let Some(path) = env::var_os("GIVEN_CONFIG") {
+            return Some(PathBuf::from(path));
+        }
+        let config_dir = env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(config_dir.join("given").join("config.toml"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_config() -> Result<()> {
+        let config = Config::parse(
+            r#"
+cache-ttl-hours = 1
+ca-certificates = ["/etc/ssl/certs/corporate-ca.pem"]
+
+[[repositories]]
+name = "Internal"
+url = "https://artifactory.example.com/maven/"
+
+[[migrations]]
+old-group = "com.example.legacy"
+new-group = "com.example"
+"#,
+        )?;
+        assert_eq!(
+            config,
+            Config {
+                repositories: vec![Repository::new(
+                    "Internal",
+                    "https://artifactory.example.com/maven"
+                )],
+                cache_ttl_hours: 1,
+                ca_certificates: vec![PathBuf::from("/etc/ssl/certs/corporate-ca.pem")],
+                migrations: vec![Migration {
+                    old_group: "com.example.legacy".to_string(),
+                    old_artifact: None,
+                    new_group: "com.example".to_string(),
+                    new_artifact: None,
+                }],
+                ..Config::default()
+            }
+        );
+        assert_eq!(config.migrations()[0], config.migrations[0]);
+
+        assert_eq!(Config::parse("")?, Config::default());
+        assert!(Config::parse("repos = []").is_err());
+        Ok(())
+    }
+}
@@ -1,8 +1,15 @@
 use crate::{
-    model::{Artifact, Group, Version},
-    parser::{get_scala_version_from_build_sbt, span::Edit, Dependency, DependencyParser, Span},
+    model::{
+        Artifact, BuildTarget, CrossVersion, DependencyKey, DependencyKind, Group, Platform,
+        Repository, Version,
+    },
+    parser::{
+        get_sbt_version_from_build_properties, mill, scala_cli::has_using_directives, span::Edit,
+        Dependency, DependencyParser, Span,
+    },
 };
 use anyhow::Result;
+use similar::TextDiff;
 use std::{collections::HashMap, path::Path};
 use std::{fs, path::PathBuf};
 
@@ -12,12 +19,70 @@ mod file_cache;
 pub struct Location {
     pub path: PathBuf,
     pub span: Span,
+    /// Whether the span covers a quoted string literal, rather than bare version text.
+    pub quoted: bool,
 }
 
 impl Location {
     pub fn new(path: PathBuf, span: Span) -> Self {
-        Self { path, span }
+        Self {
+            path,
+            span,
+            quoted: true,
+        }
     }
+
+    /// A location of a bare version, e.g. inside a larger string literal.
+    pub fn unquoted(path: PathBuf, span: Span) -> Self {
+        Self {
+            path,
+            span,
+            quoted: false,
+        }
+    }
+
+    fn replacement(&self, text: &str) -> String {
+        if self.quoted {
+            format!("\"{}\"", text)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// Where a dependency's group and artifact are written, so that they can be
+/// rewritten when it moves to new coordinates.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoordinateLocation {
+    pub group: Location,
+    pub artifact: Location,
+}
+
+impl CoordinateLocation {
+    /// The group and artifact as quoted string literals, e.g. `"dev.zio" %% "zio"`.
+    pub fn quoted(path: PathBuf, group: Span, artifact: Span) -> Self {
+        Self {
+            group: Location::new(path.clone(), group),
+            artifact: Location::new(path, artifact),
+        }
+    }
+
+    /// The group and artifact inside a larger string, e.g. `ivy"dev.zio::zio:2.1.0"`.
+    pub fn unquoted(path: PathBuf, group: Span, artifact: Span) -> Self {
+        Self {
+            group: Location::unquoted(path.clone(), group),
+            artifact: Location::unquoted(path, artifact),
+        }
+    }
+}
+
+/// Moves a dependency to a new group and artifact, rewriting them wherever
+/// they are written.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoordinateUpdate {
+    pub group: Group,
+    pub artifact: Artifact,
+    pub coordinates: Vec<CoordinateLocation>,
 }
 
 // A particular group and artifact might exist in the codebase at MULTIPLE locations.
@@ -26,13 +91,31 @@ impl Location {
 pub struct VersionWithLocations {
     pub version: Version,
     pub locations: Vec<Location>,
+    pub kind: DependencyKind,
+    pub cross: CrossVersion,
+    /// Where the group and artifact are written, for each declaration.
+    pub coordinates: Vec<CoordinateLocation>,
+    /// The configurations it is declared in, e.g. `Test`, if any.
+    pub configurations: Vec<String>,
+    /// The classifiers it is declared with, e.g. `tests`, if any.
+    pub classifiers: Vec<String>,
 }
 
 impl VersionWithLocations {
-    pub fn new(version: &Version, location: &Location) -> Self {
+    pub fn new(
+        version: &Version,
+        location: &Location,
+        kind: DependencyKind,
+        cross: CrossVersion,
+    ) -> Self {
         Self {
             version: version.clone(),
             locations: vec![location.clone()],
+            kind,
+            cross,
+            coordinates: Vec::new(),
+            configurations: Vec::new(),
+            classifiers: Vec::new(),
         }
     }
 
@@ -43,24 +126,96 @@ impl VersionWithLocations {
         }
         self.locations.push(location.clone());
     }
+
+    fn add_coordinates(&mut self, coordinates: &Option<CoordinateLocation>) {
+        if let Some(coordinates) = coordinates {
+            if !self.coordinates.contains(coordinates) {
+                self.coordinates.push(coordinates.clone());
+            }
+        }
+    }
+
+    fn add_usage(&mut self, configuration: &Option<String>, classifier: &Option<String>) {
+        for (value, values) in [
+            (configuration, &mut self.configurations),
+            (classifier, &mut self.classifiers),
+        ] {
+            if let Some(value) = value {
+                if !values.contains(value) {
+                    values.push(value.clone());
+                }
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct DependencyMap {
-    map: HashMap<(Group, Artifact), VersionWithLocations>,
+    map: HashMap<DependencyKey, VersionWithLocations>,
+    platforms: Vec<Platform>,
+    repositories: Vec<Repository>,
 }
 
 impl DependencyMap {
     pub fn iter(
         &self,
-    ) -> std::collections::hash_map::Iter<(Group, Artifact), VersionWithLocations> {
+    ) -> std::collections::hash_map::Iter<'_, DependencyKey, VersionWithLocations> {
         self.map.iter()
     }
+
+    /// The Scala versions of the build, as synthesized from `scalaVersion` and
+    /// `crossScalaVersions`, oldest first.
+    pub fn scala_versions(&self) -> Vec<&Version> {
+        let mut versions: Vec<&Version> = self
+            .map
+            .iter()
+            .filter(|(key, _)| {
+                key.group.value == "org.scala-lang"
+                    && (key.artifact.value == "scala-library"
+                        || key.artifact.value == "scala3-library_3")
+            })
+            .map(|(_, version)| &version.version)
+            .collect();
+        versions.sort();
+        versions
+    }
+
+    /// The sbt launcher version, as read from `project/build.properties`.
+    pub fn sbt_version(&self) -> Option<&Version> {
+        self.map
+            .get(&DependencyKey::new(
+                Group::new("org.scala-sbt"),
+                Artifact::new("sbt"),
+            ))
+            .map(|version| &version.version)
+    }
+
+    /// The repositories declared by the build, e.g. with `resolvers += ...`.
+    pub fn repositories(&self) -> &[Repository] {
+        &self.repositories
+    }
+
+    pub fn build_target(&self) -> BuildTarget {
+        let scala_native_version = self
+            .map
+            .get(&DependencyKey::new(
+                Group::new("org.scala-native"),
+                Artifact::new("sbt-scala-native"),
+            ))
+            .map(|version| version.version.clone());
+
+        BuildTarget {
+            scala_versions: self.scala_versions().into_iter().cloned().collect(),
+            sbt_version: self.sbt_version().cloned(),
+            platforms: self.platforms.clone(),
+            scala_native_version,
+        }
+    }
 }
 
 impl IntoIterator for DependencyMap {
-    type Item = ((Group, Artifact), VersionWithLocations);
-    type IntoIter = std::collections::hash_map::IntoIter<(Group, Artifact), VersionWithLocations>;
+    type Item = (DependencyKey, VersionWithLocations);
+    type IntoIter = std::collections::hash_map::IntoIter<DependencyKey, VersionWithLocations>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.map.into_iter()
@@ -77,6 +232,8 @@ impl DependencyMap {
     pub fn new() -> Self {
         Self {
             map: HashMap::new(),
+            platforms: Vec::new(),
+            repositories: Vec::new(),
         }
     }
 
@@ -88,18 +245,96 @@ impl DependencyMap {
         map
     }
 
+    /// Records the platforms the build targets, as detected while parsing.
+    pub fn with_platforms(mut self, mut platforms: Vec<Platform>) -> Self {
+        platforms.sort();
+        platforms.dedup();
+        self.platforms = platforms;
+        self
+    }
+
+    /// Records the repositories declared by the build, dropping duplicates.
+    pub fn with_repositories(mut self, repositories: Vec<Repository>) -> Self {
+        for repository in repositories {
+            if !self.repositories.iter().any(|r| r.url == repository.url) {
+                self.repositories.push(repository);
+            }
+        }
+        self
+    }
+
     pub fn add_dependency(&mut self, dependency: &Dependency) {
-        let key = (dependency.group.clone(), dependency.artifact.clone());
+        let key = dependency.key();
         let location = &dependency.version.location;
-        self.map
+        let entry = self
+            .map
             .entry(key)
             .and_modify(|existing| existing.add(&dependency.version.value, location))
-            .or_insert_with(|| VersionWithLocations::new(&dependency.version.value, location));
+            .or_insert_with(|| {
+                VersionWithLocations::new(
+                    &dependency.version.value,
+                    location,
+                    dependency.kind,
+                    dependency.cross,
+                )
+            });
+        entry.add_coordinates(&dependency.coordinates);
+        entry.add_usage(&dependency.configuration, &dependency.classifier);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildTool {
+    Sbt,
+    Mill,
+    ScalaCli,
+}
+
+impl BuildTool {
+    /// Detects the build tool used by the project at `project_path`, if any.
+    pub fn detect(project_path: &Path) -> Option<BuildTool> {
+        if project_path.join("build.sbt").exists() {
+            Some(BuildTool::Sbt)
+        } else if project_path.join("build.mill").exists() || project_path.join("build.sc").exists()
+        {
+            Some(BuildTool::Mill)
+        } else if project_path.join("project.scala").exists()
+            || Self::has_top_level_using_directives(project_path)
+        {
+            Some(BuildTool::ScalaCli)
+        } else {
+            None
+        }
+    }
+
+    fn has_top_level_using_directives(project_path: &Path) -> bool {
+        let Ok(entries) = fs::read_dir(project_path) else {
+            return false;
+        };
+        entries.flatten().any(|entry| {
+            let path = entry.path();
+            path.is_file()
+                && path
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| e == "scala" || e == "sc")
+                && fs::read_to_string(&path).is_ok_and(|code| has_using_directives(&code))
+        })
+    }
+}
+
+/// Collects the dependencies of the project, using whichever build tool it uses.
+pub fn collect_dependencies(project_path: &Path) -> Result<DependencyMap> {
+    match BuildTool::detect(project_path) {
+        Some(BuildTool::Mill) => collect_mill_dependencies(project_path),
+        Some(BuildTool::ScalaCli) => collect_scala_cli_dependencies(project_path),
+        Some(BuildTool::Sbt) | None => collect_sbt_dependencies(project_path),
     }
 }
 
 /// - read build.sbt
 /// - read every scala file in the project folder
+/// - read the `//> using` directives of the project's scripts
 pub fn collect_sbt_dependencies(project_path: &Path) -> Result<DependencyMap> {
     let mut dependency_parser = DependencyParser::new();
     let all_dependency_paths = all_dependency_paths(project_path);
@@ -117,44 +352,195 @@ pub fn collect_sbt_dependencies(project_path: &Path) -> Result<DependencyMap> {
         dependency_parser.parse_dependencies(path, &code);
     }
 
-    let mut dependencies = dependency_parser.dependencies;
+    parse_script_directives(project_path, &mut dependency_parser, &mut file_cache)?;
 
-    // attempt to parse scala version from build.sbt
+    // attempt to parse the scala versions from build.sbt
     let build_sbt_path = project_path.join("build.sbt");
     if build_sbt_path.exists() {
         let code = file_cache.read_to_string(&build_sbt_path)?;
-        if let Some(scala_version) = get_scala_version_from_build_sbt(&build_sbt_path, &code) {
-            dependencies.push(scala_version);
+        dependency_parser.parse_scala_versions(&build_sbt_path, &code);
+    }
+
+    let mut dependencies = dependency_parser.dependencies;
+
+    // attempt to parse the sbt launcher version from project/build.properties
+    let build_properties_path = project_path.join("project/build.properties");
+    if build_properties_path.exists() {
+        let code = file_cache.read_to_string(&build_properties_path)?;
+        if let Some(sbt_version) =
+            get_sbt_version_from_build_properties(&build_properties_path, &code)
+        {
+            dependencies.push(sbt_version);
         }
     }
 
-    Ok(DependencyMap::from_dependencies(dependencies))
+    Ok(DependencyMap::from_dependencies(dependencies)
+        .with_platforms(dependency_parser.platforms)
+        .with_repositories(dependency_parser.repositories))
+}
+
+/// The result of applying version updates to a single file, before it is written.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileUpdate {
+    pub path: PathBuf,
+    pub original: String,
+    pub updated: String,
+}
+
+impl FileUpdate {
+    /// Renders a git-style unified diff, with paths shown relative to `root`.
+    pub fn unified_diff(&self, root: &Path) -> String {
+        let path = self.path.strip_prefix(root).unwrap_or(&self.path);
+        TextDiff::from_lines(&self.original, &self.updated)
+            .unified_diff()
+            .context_radius(3)
+            .header(
+                &format!("a/{}", path.display()),
+                &format!("b/{}", path.display()),
+            )
+            .to_string()
+    }
+}
+
+/// - read build.mill / build.sc, and every other Mill build file
+/// - read the pinned Mill version from .mill-version
+/// - read the `//> using` directives of the project's scripts
+pub fn collect_mill_dependencies(project_path: &Path) -> Result<DependencyMap> {
+    let mut dependency_parser = DependencyParser::new();
+    let mut paths = Vec::new();
+    collect_files(project_path, &["mill", "sc"], &mut paths);
+    let mut file_cache = file_cache::FileCache::new();
+
+    // load all val defs from all files
+    for path in &paths {
+        let code = file_cache.read_to_string(path)?;
+        dependency_parser.parse_val_defs(path, &code);
+    }
+
+    // load all dependencies from all files
+    for path in &paths {
+        let code = file_cache.read_to_string(path)?;
+        dependency_parser.parse_mill_dependencies(path, &code);
+    }
+
+    parse_script_directives(project_path, &mut dependency_parser, &mut file_cache)?;
+
+    let mut dependencies = dependency_parser.dependencies;
+
+    let mill_version_path = project_path.join(".mill-version");
+    if mill_version_path.exists() {
+        let code = file_cache.read_to_string(&mill_version_path)?;
+        if let Some(mill_version) = mill::get_mill_version(&mill_version_path, &code) {
+            dependencies.push(mill_version);
+        }
+    }
+
+    Ok(DependencyMap::from_dependencies(dependencies)
+        .with_platforms(dependency_parser.platforms)
+        .with_repositories(dependency_parser.repositories))
+}
+
+/// - read the `//> using` directives of every .scala and .sc file
+pub fn collect_scala_cli_dependencies(project_path: &Path) -> Result<DependencyMap> {
+    let mut dependency_parser = DependencyParser::new();
+    let mut paths = Vec::new();
+    collect_files(project_path, &["scala", "sc"], &mut paths);
+
+    for path in &paths {
+        let code = fs::read_to_string(path)?;
+        dependency_parser.parse_using_directives(path, &code);
+    }
+
+    Ok(
+        DependencyMap::from_dependencies(dependency_parser.dependencies)
+            .with_platforms(dependency_parser.platforms)
+            .with_repositories(dependency_parser.repositories),
+    )
+}
+
+/// scala-cli scripts and tools kept inside an sbt or Mill project declare their
+/// own dependencies with `//> using` directives.
+fn parse_script_directives(
+    project_path: &Path,
+    dependency_parser: &mut DependencyParser,
+    file_cache: &mut file_cache::FileCache,
+) -> Result<()> {
+    let mut paths = Vec::new();
+    collect_files(project_path, &["scala", "sc"], &mut paths);
+    for path in &paths {
+        let code = file_cache.read_to_string(path)?;
+        dependency_parser.parse_script_directives(path, &code);
+    }
+    Ok(())
 }
 
 pub fn write_version_updates(updates: &[(Version, Vec<Location>)]) -> std::io::Result<()> {
+    write_updates(updates, &[])
+}
+
+/// Like [`write_version_updates`], additionally moving dependencies to new coordinates.
+pub fn write_updates(
+    updates: &[(Version, Vec<Location>)],
+    coordinate_updates: &[CoordinateUpdate],
+) -> std::io::Result<()> {
+    for file_update in plan_updates(updates, coordinate_updates)? {
+        fs::write(file_update.path, file_update.updated)?;
+    }
+
+    Ok(())
+}
+
+/// Computes the new contents of every file touched by `updates`, without writing
+/// anything. Files are returned sorted by path.
+pub fn plan_version_updates(
+    updates: &[(Version, Vec<Location>)],
+) -> std::io::Result<Vec<FileUpdate>> {
+    plan_updates(updates, &[])
+}
+
+/// Like [`plan_version_updates`], additionally rewriting the group and artifact
+/// of the dependencies moving to new coordinates.
+pub fn plan_updates(
+    updates: &[(Version, Vec<Location>)],
+    coordinate_updates: &[CoordinateUpdate],
+) -> std::io::Result<Vec<FileUpdate>> {
     // Step 1: Group updates by file path
     let mut updates_by_file: HashMap<PathBuf, Vec<Edit>> = HashMap::new();
+    let mut add_edit = |location: &Location, text: &str| {
+        updates_by_file
+            .entry(location.path.clone())
+            .or_default()
+            .push(Edit {
+                span: location.span.clone(),
+                text: location.replacement(text),
+            });
+    };
     for (version, locations) in updates {
         for location in locations {
-            let edit = Edit {
-                span: location.span.clone(),
-                text: format!("\"{}\"", version),
-            };
-            updates_by_file
-                .entry(location.path.clone())
-                .or_default()
-                .push(edit);
+            add_edit(location, &version.to_string());
+        }
+    }
+    for update in coordinate_updates {
+        for coordinates in &update.coordinates {
+            add_edit(&coordinates.group, &update.group.value);
+            add_edit(&coordinates.artifact, &update.artifact.value);
         }
     }
 
-    // Step 2: Apply edits and write back to files
+    // Step 2: Apply edits to each file's contents
+    let mut file_updates = Vec::new();
     for (file_path, edits) in updates_by_file {
-        let original_content = fs::read_to_string(&file_path)?;
-        let updated_content = Edit::apply_edits(edits, &original_content);
-        fs::write(file_path, updated_content)?;
+        let original = fs::read_to_string(&file_path)?;
+        let updated = Edit::apply_edits(edits, &original);
+        file_updates.push(FileUpdate {
+            path: file_path,
+            original,
+            updated,
+        });
     }
+    file_updates.sort_by(|a, b| a.path.cmp(&b.path));
 
-    Ok(())
+    Ok(file_updates)
 }
 
 fn all_dependency_paths(project_path: &Path) -> Vec<PathBuf> {
@@ -163,17 +549,31 @@ fn all_dependency_paths(project_path: &Path) -> Vec<PathBuf> {
         project_path.join("project/plugins.sbt"),
     ];
 
-    collect_scala_files(project_path, &mut paths);
+    collect_files(project_path, &["scala"], &mut paths);
     paths.into_iter().filter(|path| path.exists()).collect()
 }
 
-fn collect_scala_files(dir: &Path, paths: &mut Vec<PathBuf>) {
+/// Directories holding build output, which may contain generated copies of build files.
+const OUTPUT_DIRS: [&str; 2] = ["out", ".scala-build"];
+
+fn collect_files(dir: &Path, extensions: &[&str], paths: &mut Vec<PathBuf>) {
     if let Ok(entries) = fs::read_dir(dir) {
         for entry in entries.flatten() {
             let path = entry.path();
             if path.is_dir() {
-                collect_scala_files(&path, paths);
-            } else if path.extension().and_then(|e| e.to_str()) == Some("scala") {
+                if path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| OUTPUT_DIRS.contains(&n))
+                {
+                    continue;
+                }
+                collect_files(&path, extensions, paths);
+            } else if path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| extensions.contains(&e))
+            {
                 paths.push(path);
             }
         }
@@ -188,7 +588,7 @@ mod tests {
     #[test]
     fn test_collect_dependencies_from_dir() {
         let path = Path::new("/Users/kit/code/archive/scala-update-2/");
-        let result = collect_sbt_dependencies(&path);
+        let result = collect_sbt_dependencies(path);
         if let Ok(deps) = result {
             for (_, dep) in deps.map.iter() {
                 println!("{:?}", dep);
@@ -202,6 +602,335 @@ mod tests {
     use std::io::Write;
     use tempfile::tempdir;
 
+    #[test]
+    fn test_plan_version_updates_diff() -> std::io::Result<()> {
+        let dir = tempdir()?;
+        let build_sbt_path = dir.path().join("build.sbt");
+        let versions_scala_path = dir.path().join("project/Versions.scala");
+        fs::create_dir_all(versions_scala_path.parent().unwrap())?;
+        fs::write(
+            &build_sbt_path,
+            "libraryDependencies ++= Seq(\n  \"dev.zio\" %% \"zio\" % Versions.zio\n)\n",
+        )?;
+        fs::write(
+            &versions_scala_path,
+            "object Versions {\n  val zio = \"2.0.0\"\n}\n",
+        )?;
+
+        let dependencies = collect_sbt_dependencies(dir.path()).unwrap();
+        let updates: Vec<(Version, Vec<Location>)> = dependencies
+            .map
+            .values()
+            .map(|dep| (Version::new("2.1.0"), dep.locations.clone()))
+            .collect();
+
+        let file_updates = plan_version_updates(&updates)?;
+        assert_eq!(file_updates.len(), 1);
+        assert_eq!(
+            file_updates[0].unified_diff(dir.path()),
+            "\
+--- a/project/Versions.scala
++++ b/project/Versions.scala
+@@ -1,3 +1,3 @@
+ object Versions {
+-  val zio = \"2.0.0\"
++  val zio = \"2.1.0\"
+ }
+"
+        );
+
+        // nothing is written
+        assert_eq!(
+            fs::read_to_string(&versions_scala_path)?,
+            "object Versions {\n  val zio = \"2.0.0\"\n}\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sbt_version_update_preserves_build_properties() -> std::io::Result<()> {
+        let dir = tempdir()?;
+        let build_properties_path = dir.path().join("project/build.properties");
+        fs::create_dir_all(build_properties_path.parent().unwrap())?;
+        fs::write(dir.path().join("build.sbt"), "scalaVersion := \"3.3.1\"\n")?;
+        fs::write(
+            &build_properties_path,
+            "# pinned launcher\nsbt.version = 1.9.9\n",
+        )?;
+
+        let dependencies = collect_sbt_dependencies(dir.path()).unwrap();
+        let sbt = &dependencies.map
+            [&DependencyKey::new(Group::new("org.scala-sbt"), Artifact::new("sbt"))];
+        assert_eq!(sbt.version, Version::new("1.9.9"));
+        assert_eq!(sbt.kind, DependencyKind::Toolchain);
+
+        write_version_updates(&[(Version::new("1.10.0"), sbt.locations.clone())])?;
+        assert_eq!(
+            fs::read_to_string(&build_properties_path)?,
+            "# pinned launcher\nsbt.version = 1.10.0\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cross_scala_versions_update_independently() -> std::io::Result<()> {
+        let dir = tempdir()?;
+        let build_sbt_path = dir.path().join("build.sbt");
+        fs::create_dir_all(dir.path().join("project"))?;
+        fs::write(
+            dir.path().join("project/Versions.scala"),
+            "object Versions {\n  val scala212 = \"2.12.18\"\n  val scala213 = \"2.13.12\"\n}\n",
+        )?;
+        fs::write(
+            &build_sbt_path,
+            r#"ThisBuild / scalaVersion := Versions.scala213
+crossScalaVersions := Seq(Versions.scala212, Versions.scala213, "3.3.1", "3.5.0")
+"#,
+        )?;
+
+        let dependencies = collect_sbt_dependencies(dir.path()).unwrap();
+        assert_eq!(
+            dependencies.build_target().scala_versions,
+            vec![
+                Version::new("2.12.18"),
+                Version::new("2.13.12"),
+                Version::new("3.3.1"),
+                Version::new("3.5.0")
+            ]
+        );
+
+        let library = |artifact: &str, line: &str| {
+            let key = DependencyKey::new(Group::new("org.scala-lang"), Artifact::new(artifact));
+            dependencies.map[&key.with_line(line)].clone()
+        };
+        let scala212 = library("scala-library", "2.12");
+        assert_eq!(scala212.version, Version::new("2.12.18"));
+        assert_eq!(scala212.locations.len(), 1);
+        assert_eq!(
+            library("scala-library", "2.13").version,
+            Version::new("2.13.12")
+        );
+        // the 3.3 LTS and 3.5 are updated apart as well
+        let scala33 = library("scala3-library_3", "3.3");
+        assert_eq!(scala33.version, Version::new("3.3.1"));
+        assert_eq!(scala33.locations.len(), 1);
+        assert_eq!(scala33.locations[0].path, build_sbt_path);
+        let scala35 = library("scala3-library_3", "3.5");
+        assert_eq!(scala35.version, Version::new("3.5.0"));
+        assert_eq!(scala35.locations.len(), 1);
+
+        write_version_updates(&[(Version::new("2.12.19"), scala212.locations)])?;
+        assert_eq!(
+            fs::read_to_string(dir.path().join("project/Versions.scala"))?,
+            "object Versions {\n  val scala212 = \"2.12.19\"\n  val scala213 = \"2.13.12\"\n}\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_collect_sbt_plugins() -> std::io::Result<()> {
+        let dir = tempdir()?;
+        fs::create_dir_all(dir.path().join("project"))?;
+        fs::write(
+            dir.path().join("build.sbt"),
+            "libraryDependencies += \"dev.zio\" %% \"zio\" % \"2.0.0\"\n",
+        )?;
+        fs::write(
+            dir.path().join("project/plugins.sbt"),
+            "addSbtPlugin(\"org.scalameta\" % \"sbt-scalafmt\" % \"2.5.2\")\n",
+        )?;
+        fs::write(
+            dir.path().join("project/build.properties"),
+            "sbt.version=1.9.9\n",
+        )?;
+
+        let dependencies = collect_sbt_dependencies(dir.path()).unwrap();
+        let kind_of = |group: &str, artifact: &str| {
+            dependencies.map[&DependencyKey::new(Group::new(group), Artifact::new(artifact))].kind
+        };
+        assert_eq!(
+            kind_of("org.scalameta", "sbt-scalafmt"),
+            DependencyKind::SbtPlugin
+        );
+        assert_eq!(kind_of("dev.zio", "zio"), DependencyKind::Library);
+        assert_eq!(
+            dependencies.build_target(),
+            BuildTarget {
+                scala_versions: Vec::new(),
+                sbt_version: Some(Version::new("1.9.9")),
+                ..Default::default()
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_collect_script_directives_in_builds() -> std::io::Result<()> {
+        let dir = tempdir()?;
+        fs::create_dir_all(dir.path().join("scripts"))?;
+        fs::write(
+            dir.path().join("build.sbt"),
+            "scalaVersion := \"2.13.14\"\nlibraryDependencies += \"dev.zio\" %% \"zio\" % \"2.0.0\"\n",
+        )?;
+        fs::write(
+            dir.path().join("scripts/release.sc"),
+            "//> using dep \"com.lihaoyi::os-lib:0.9.1\"\n\nprintln(os.pwd)\n",
+        )?;
+
+        fs::write(
+            dir.path().join("scripts/Tool.scala"),
+            "//> using scala 3.3.1\n\nobject Tool\n",
+        )?;
+
+        let dependencies = collect_dependencies(dir.path()).unwrap();
+        // the script's Scala version doesn't constrain the build's libraries
+        assert_eq!(
+            dependencies.build_target(),
+            BuildTarget {
+                scala_versions: vec![Version::new("2.13.14")],
+                ..Default::default()
+            }
+        );
+        let os_lib = &dependencies.map
+            [&DependencyKey::new(Group::new("com.lihaoyi"), Artifact::new("os-lib"))];
+        assert_eq!(os_lib.version, Version::new("0.9.1"));
+        assert!(dependencies.map.contains_key(&DependencyKey::new(
+            Group::new("dev.zio"),
+            Artifact::new("zio")
+        )));
+
+        fs::remove_file(dir.path().join("build.sbt"))?;
+        fs::write(
+            dir.path().join("build.mill"),
+            "object foo extends ScalaModule {\n  def ivyDeps = Agg(ivy\"dev.zio::zio:2.0.0\")\n}\n",
+        )?;
+        let dependencies = collect_dependencies(dir.path()).unwrap();
+        assert!(dependencies.map.contains_key(&DependencyKey::new(
+            Group::new("com.lihaoyi"),
+            Artifact::new("os-lib")
+        )));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_full_stack_migration() -> std::io::Result<()> {
+        let dir = tempdir()?;
+        fs::create_dir_all(dir.path().join("project"))?;
+        let plugins_path = dir.path().join("project/plugins.sbt");
+        fs::write(dir.path().join("build.sbt"), "")?;
+        fs::write(
+            &plugins_path,
+            "addSbtPlugin(\"com.typesafe.sbt\" % \"sbt-native-packager\" % \"1.8.1\")\n",
+        )?;
+
+        let dependencies = collect_sbt_dependencies(dir.path()).unwrap();
+        let dep = &dependencies.map[&DependencyKey::new(
+            Group::new("com.typesafe.sbt"),
+            Artifact::new("sbt-native-packager"),
+        )];
+        write_updates(
+            &[(Version::new("1.10.4"), dep.locations.clone())],
+            &[CoordinateUpdate {
+                group: Group::new("com.github.sbt"),
+                artifact: Artifact::new("sbt-native-packager"),
+                coordinates: dep.coordinates.clone(),
+            }],
+        )?;
+
+        assert_eq!(
+            fs::read_to_string(&plugins_path)?,
+            "addSbtPlugin(\"com.github.sbt\" % \"sbt-native-packager\" % \"1.10.4\")\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_full_stack_mill_version_update() -> std::io::Result<()> {
+        let dir = tempdir()?;
+        let build_mill_path = dir.path().join("build.mill");
+        let mill_version_path = dir.path().join(".mill-version");
+        fs::write(
+            &build_mill_path,
+            r#"
+object Versions {
+  val zio = "2.0.0"
+}
+
+object foo extends ScalaModule {
+  def scalaVersion = "3.3.1"
+  def ivyDeps = Agg(
+    ivy"dev.zio::zio:${Versions.zio}",
+    ivy"com.lihaoyi::scalatags:0.12.0"
+  )
+}
+"#,
+        )?;
+        fs::write(&mill_version_path, "0.11.7\n")?;
+
+        assert_eq!(BuildTool::detect(dir.path()), Some(BuildTool::Mill));
+        let dependencies = collect_dependencies(dir.path()).unwrap();
+        assert_eq!(dependencies.map.len(), 4);
+
+        let updates: Vec<(Version, Vec<Location>)> = dependencies
+            .map
+            .values()
+            .map(|dep| (Version::new("999.999.999"), dep.locations.clone()))
+            .collect();
+        write_version_updates(&updates)?;
+
+        assert_eq!(
+            fs::read_to_string(&build_mill_path)?,
+            r#"
+object Versions {
+  val zio = "999.999.999"
+}
+
+object foo extends ScalaModule {
+  def scalaVersion = "999.999.999"
+  def ivyDeps = Agg(
+    ivy"dev.zio::zio:${Versions.zio}",
+    ivy"com.lihaoyi::scalatags:999.999.999"
+  )
+}
+"#
+        );
+        assert_eq!(fs::read_to_string(&mill_version_path)?, "999.999.999\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_full_stack_scala_cli_version_update() -> std::io::Result<()> {
+        let dir = tempdir()?;
+        let script_path = dir.path().join("hello.sc");
+        fs::write(
+            &script_path,
+            "//> using scala 3.3.1\n//> using dep \"com.lihaoyi::os-lib:0.9.1\"\n\nprintln(os.pwd)\n",
+        )?;
+
+        assert_eq!(BuildTool::detect(dir.path()), Some(BuildTool::ScalaCli));
+        let dependencies = collect_dependencies(dir.path()).unwrap();
+        let updates: Vec<(Version, Vec<Location>)> = dependencies
+            .map
+            .values()
+            .map(|dep| (Version::new("999.999.999"), dep.locations.clone()))
+            .collect();
+        write_version_updates(&updates)?;
+
+        assert_eq!(
+            fs::read_to_string(&script_path)?,
+            "//> using scala 999.999.999\n//> using dep \"com.lihaoyi::os-lib:999.999.999\"\n\nprintln(os.pwd)\n",
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_full_stack_version_update() -> std::io::Result<()> {
         // Step 1: Create a temporary directory and sample build.sbt and versions.scala files
@@ -244,7 +973,7 @@ object Versions {{
 
         // Step 2: Read the dependencies from the files
         println!("Reading dependencies from the files...");
-        let dependencies = collect_sbt_dependencies(&dir.path());
+        let dependencies = collect_sbt_dependencies(dir.path());
         println!("Dependencies read: {:?}", dependencies);
 
         // Step 3: Select new versions for the dependencies
@@ -252,8 +981,8 @@ object Versions {{
         let updates: Vec<(Version, Vec<Location>)> = dependencies
             .unwrap()
             .map
-            .iter()
-            .map(|(_, dep)| (Version::new("999.999.999"), dep.locations.clone()))
+            .values()
+            .map(|dep| (Version::new("999.999.999"), dep.locations.clone()))
             .collect();
         println!("Updates selected: {:?}", updates);
 
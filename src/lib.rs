@@ -1,5 +1,7 @@
 pub mod cli;
+pub mod config;
 pub mod dependency_resolver;
 pub mod model;
 pub mod package_search;
 pub mod parser;
+pub mod release_notes;
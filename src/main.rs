@@ -1,43 +1,165 @@
 use altar::*;
+use clap::Parser;
 use given::{
-    cli,
-    dependency_resolver::{write_version_updates, Location},
+    cli::{self, check, Entry},
+    config::Config,
+    dependency_resolver::{plan_updates, write_updates, BuildTool, CoordinateUpdate, Location},
     model::*,
+    package_search::maven::MavenPackageSearch,
+    release_notes::ReleaseNotesClient,
 };
-use std::cmp;
+use std::{cmp, time::Duration};
 
-// TODO: Support Mill projects
 fn is_valid_scala_project() -> bool {
-    std::path::Path::new("build.sbt").exists()
+    BuildTool::detect(std::path::Path::new(".")).is_some()
 }
 
 #[tokio::main]
 async fn main() {
+    let args = cli::Args::parse();
+
     // 1. Fail if the current directory is not a valid Scala project
     if !is_valid_scala_project() {
         render_invalid_project_message();
+        if args.is_headless() {
+            std::process::exit(check::EXIT_ERROR);
+        }
+        return;
+    }
+
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("error: {:#}", err);
+            std::process::exit(check::EXIT_ERROR);
+        }
+    };
+    let current_dir = std::env::current_dir().unwrap();
+    let maven_package_search = match MavenPackageSearch::from_config(&config, &current_dir) {
+        Ok(search) => search.offline(args.offline),
+        Err(err) => {
+            eprintln!("error: {:#}", err);
+            std::process::exit(check::EXIT_ERROR);
+        }
+    };
+
+    let min_age =
+        Duration::from_secs(args.min_age_days.unwrap_or(config.min_age_days) * 24 * 60 * 60);
+
+    if args.is_headless() {
+        let exit_code = check::run(
+            &current_dir,
+            &maven_package_search,
+            &args.fail_on,
+            args.format,
+            min_age,
+        )
+        .await;
+        std::process::exit(exit_code);
+    }
+
+    if args.is_unattended() {
+        let mut entry_map =
+            match cli::load_entry_map(&current_dir, &maven_package_search, min_age).await {
+                Ok(entry_map) => entry_map,
+                Err(err) => {
+                    eprintln!("error: {:#}", err);
+                    std::process::exit(check::EXIT_ERROR);
+                }
+            };
+        for (group, artifact) in entry_map.select_by_policy(&args.apply_policy()) {
+            eprintln!(
+                "warning: skipped {}:{}, whose version is shared with dependencies that have no update in common",
+                group, artifact
+            );
+        }
+        for (group, artifact, entry) in entry_map.with_errors() {
+            eprintln!(
+                "warning: could not check {}:{}: {}",
+                group,
+                artifact,
+                entry.lookup_error.unwrap_or_default()
+            );
+        }
+        for (group, artifact, entry) in entry_map.sorted() {
+            if let Some(relocation) = entry.relocation {
+                eprintln!(
+                    "note: {}:{} has moved to {}:{}, run given interactively to migrate",
+                    group, artifact, relocation.group, relocation.artifact
+                );
+            }
+        }
+
+        let entries: Vec<_> = entry_map.selected().collect();
+        if args.dry_run {
+            print_update_diffs(&entries, &[]);
+        } else {
+            process_updates(&entries, &[]);
+            render_updated_message(&entries, &[]);
+        }
         return;
     }
 
-    let mut app = cli::SupApp::default();
+    let release_notes_client = ReleaseNotesClient::new(
+        maven_package_search.client().clone(),
+        &config.github_api_url,
+    );
+    let mut app =
+        cli::SupApp::new(maven_package_search, min_age).with_release_notes(release_notes_client);
     app.run(false).await;
     if app.decided_to_update {
         let entries: Vec<_> = app.entry_map.selected().collect();
+        let migrations: Vec<_> = app.entry_map.selected_migrations().collect();
 
-        process_updates(&entries);
-        render_updated_message(&entries)
+        if args.dry_run {
+            print_update_diffs(&entries, &migrations);
+        } else {
+            process_updates(&entries, &migrations);
+            render_updated_message(&entries, &migrations)
+        }
     } else if app.entry_map.with_updates().is_empty() {
-        render_no_updates();
+        let failures = app.entry_map.with_errors();
+        if failures.is_empty() {
+            render_no_updates();
+        } else {
+            println!(
+                "{}",
+                cli::render_lookup_failures(&failures).padding_v(1).as_str()
+            );
+        }
     } else {
         render_quit_message();
     }
 }
 
-fn render_updated_message(entries: &[(&Group, &Artifact, &Version, &Version, &Vec<Location>)]) {
+fn render_updated_message(
+    entries: &[(&Group, &Artifact, &Version, &Version, &Vec<Location>)],
+    migrations: &[&Entry],
+) {
+    // Migrations are listed as updates to their new coordinates.
+    let rows: Vec<_> = entries
+        .iter()
+        .map(|(group, artifact, old_version, new_version, _)| {
+            (*group, *artifact, *old_version, new_version.to_string())
+        })
+        .chain(migrations.iter().filter_map(|entry| {
+            let relocation = entry.relocation.as_ref()?;
+            let new_coordinates = format!(
+                "{}:{} {}",
+                relocation.group, relocation.artifact, relocation.version
+            );
+            Some((
+                &entry.group,
+                &entry.artifact,
+                &entry.version,
+                new_coordinates,
+            ))
+        }))
+        .collect();
+
     let (max_group_width, max_artifact_width, max_old_version_width) =
-        entries
-            .iter()
-            .fold((0, 0, 0), |acc, (group, artifact, old_version, _, _)| {
+        rows.iter()
+            .fold((0, 0, 0), |acc, (group, artifact, old_version, _)| {
                 (
                     cmp::max(acc.0, group.value.len()),
                     cmp::max(acc.1, artifact.value.len()),
@@ -45,10 +167,10 @@ fn render_updated_message(entries: &[(&Group, &Artifact, &Version, &Version, &Ve
                 )
             });
 
-    let views: Vec<_> = entries
+    let views: Vec<_> = rows
         .iter()
         .enumerate()
-        .map(|(i, (group, artifact, old_version, new_version, _))| {
+        .map(|(i, (group, artifact, old_version, new_version))| {
             hstack((
                 format!("{:>width$}.", i + 1, width = 3).dim(),
                 format!("{:<width$}", group.value, width = max_group_width),
@@ -61,9 +183,10 @@ fn render_updated_message(entries: &[(&Group, &Artifact, &Version, &Version, &Ve
                 )
                 .dim(),
                 text("→").dim(),
-                new_version.to_string().green(),
+                new_version.clone().green(),
             ))
-            .id((group, artifact))
+            // the old version tells the lines of a cross-built Scala apart
+            .id((group, artifact, old_version))
         })
         .collect();
 
@@ -126,7 +249,10 @@ fn render_invalid_project_message() {
         text("  Δ GIVEN UPDATE").red(),
         hstack((
             text("  │ I cannot find a").red(),
-            text("build.sbt").red().underline(),
+            text("build.sbt,").red().underline(),
+            text("build.mill").red().underline(),
+            text("or").red(),
+            text("project.scala").red().underline(),
             text("file in this directory.").red(),
         )),
         text("  │ I have no power here.").red().dim(),
@@ -164,11 +290,43 @@ const FULLY_UPDATED_MESSAGES: [&str; 4] = [
     "Yet we must remain vigilant.",
 ];
 
-fn process_updates(entries: &[(&Group, &Artifact, &Version, &Version, &Vec<Location>)]) {
-    let version_updates = entries
+/// The version updates of the selected entries, and of the migrating ones along
+/// with the rewrites of their coordinates.
+fn updates(
+    entries: &[(&Group, &Artifact, &Version, &Version, &Vec<Location>)],
+    migrations: &[&Entry],
+) -> (Vec<(Version, Vec<Location>)>, Vec<CoordinateUpdate>) {
+    let mut version_updates: Vec<_> = entries
         .iter()
         .map(|(_, _, _, new_version, locations)| ((*new_version).clone(), (*locations).clone()))
-        .collect::<Vec<_>>();
+        .collect();
+    let mut coordinate_updates = Vec::new();
+    for entry in migrations {
+        if let (Some(relocation), Some(coordinate_update)) =
+            (&entry.relocation, entry.coordinate_update())
+        {
+            version_updates.push((relocation.version.clone(), entry.locations.clone()));
+            coordinate_updates.push(coordinate_update);
+        }
+    }
+    (version_updates, coordinate_updates)
+}
 
-    write_version_updates(&version_updates).unwrap();
+fn process_updates(
+    entries: &[(&Group, &Artifact, &Version, &Version, &Vec<Location>)],
+    migrations: &[&Entry],
+) {
+    let (version_updates, coordinate_updates) = updates(entries, migrations);
+    write_updates(&version_updates, &coordinate_updates).unwrap();
+}
+
+fn print_update_diffs(
+    entries: &[(&Group, &Artifact, &Version, &Version, &Vec<Location>)],
+    migrations: &[&Entry],
+) {
+    let current_dir = std::env::current_dir().unwrap();
+    let (version_updates, coordinate_updates) = updates(entries, migrations);
+    for file_update in plan_updates(&version_updates, &coordinate_updates).unwrap() {
+        print!("{}", file_update.unified_diff(&current_dir));
+    }
 }
@@ -0,0 +1,121 @@
+use serde::Deserialize;
+
+use super::{Artifact, Group, Version};
+
+/// A move of artifacts to new coordinates, e.g. when a project changes its groupId.
+///
+/// ```toml
+/// [[migrations]]
+/// old-group = "com.typesafe.sbt"
+/// old-artifact = "sbt-native-packager"
+/// new-group = "com.github.sbt"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Migration {
+    pub old_group: String,
+    /// The artifact that moved, or every artifact of the group if omitted.
+    pub old_artifact: Option<String>,
+    pub new_group: String,
+    /// The artifact's new name, if it was renamed along the way.
+    pub new_artifact: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct MigrationsFile {
+    migrations: Vec<Migration>,
+}
+
+impl Migration {
+    /// The migrations shipped with given, see `migrations.toml`.
+    pub fn bundled() -> Vec<Migration> {
+        let file: MigrationsFile = toml::from_str(include_str!("migrations.toml"))
+            .expect("The bundled migrations table is valid");
+        file.migrations
+    }
+
+    /// The new coordinates of the artifact, if this migration moved it.
+    pub fn apply(&self, group: &Group, artifact: &Artifact) -> Option<(Group, Artifact)> {
+        if group.value != self.old_group
+            || self
+                .old_artifact
+                .as_ref()
+                .is_some_and(|old_artifact| *old_artifact != artifact.value)
+        {
+            return None;
+        }
+        let new_artifact = self.new_artifact.as_deref().unwrap_or(&artifact.value);
+        Some((Group::new(&self.new_group), Artifact::new(new_artifact)))
+    }
+
+    /// The new coordinates of the artifact according to the first migration that moved it.
+    pub fn find(
+        migrations: &[Migration],
+        group: &Group,
+        artifact: &Artifact,
+    ) -> Option<(Group, Artifact)> {
+        migrations
+            .iter()
+            .find_map(|migration| migration.apply(group, artifact))
+    }
+}
+
+/// New coordinates a dependency has moved to, from a relocation in its POM or
+/// a known migration, along with the newest version published there.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Relocation {
+    pub group: Group,
+    pub artifact: Artifact,
+    pub version: Version,
+    /// Why the artifact moved, if its POM says so.
+    pub message: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_find_migration() {
+        let migrations = Migration::bundled();
+        assert_eq!(
+            Migration::find(
+                &migrations,
+                &Group::new("com.typesafe.sbt"),
+                &Artifact::new("sbt-native-packager")
+            ),
+            Some((
+                Group::new("com.github.sbt"),
+                Artifact::new("sbt-native-packager")
+            ))
+        );
+        assert_eq!(
+            Migration::find(
+                &migrations,
+                &Group::new("org.lyranthe.fs2-grpc"),
+                &Artifact::new("sbt-java-gen")
+            ),
+            Some((Group::new("org.typelevel"), Artifact::new("sbt-fs2-grpc")))
+        );
+        assert_eq!(
+            Migration::find(
+                &migrations,
+                &Group::new("com.typesafe.sbt"),
+                &Artifact::new("sbt-web")
+            ),
+            None
+        );
+
+        let whole_group = Migration {
+            old_group: "com.example".to_string(),
+            old_artifact: None,
+            new_group: "org.example".to_string(),
+            new_artifact: None,
+        };
+        assert_eq!(
+            whole_group.apply(&Group::new("com.example"), &Artifact::new("anything")),
+            Some((Group::new("org.example"), Artifact::new("anything")))
+        );
+    }
+}
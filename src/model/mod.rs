@@ -1,7 +1,13 @@
+pub mod migration;
+pub mod release_date;
 pub mod update_options;
 pub mod version;
 use std::fmt::Display;
 
+use serde::Deserialize;
+
+pub use migration::{Migration, Relocation};
+pub use release_date::ReleaseDate;
 pub use version::Version;
 
 #[derive(Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
@@ -41,3 +47,178 @@ impl Display for Artifact {
         write!(f, "{}", self.value)
     }
 }
+
+/// What the build tracks a dependency by: its group and artifact, and the line
+/// it is updated on when the same artifact is updated independently on several,
+/// e.g. the Scala 2.12 and 2.13 versions of a cross-built project.
+#[derive(Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
+pub struct DependencyKey {
+    pub group: Group,
+    pub artifact: Artifact,
+    pub line: Option<String>,
+}
+
+impl DependencyKey {
+    pub fn new(group: Group, artifact: Artifact) -> Self {
+        DependencyKey {
+            group,
+            artifact,
+            line: None,
+        }
+    }
+
+    pub fn with_line(mut self, line: &str) -> Self {
+        self.line = Some(line.to_string());
+        self
+    }
+}
+
+/// How a dependency is declared in the build, which determines how its versions are resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DependencyKind {
+    /// A regular library dependency, e.g. in `libraryDependencies`.
+    #[default]
+    Library,
+    /// An sbt plugin, declared with `addSbtPlugin(...)`.
+    SbtPlugin,
+    /// A Scala compiler plugin, declared with `addCompilerPlugin(...)` or
+    /// `compilerPlugin(...)`.
+    CompilerPlugin,
+    /// A version forced onto the dependency graph with `dependencyOverrides`.
+    Override,
+    /// The build's own tooling, e.g. the Scala version or the sbt launcher.
+    /// These are resolved by their exact artifact name.
+    Toolchain,
+}
+
+impl DependencyKind {
+    /// How the kind is shown next to a dependency, `None` for plain libraries.
+    pub fn label(self) -> Option<&'static str> {
+        match self {
+            DependencyKind::Library => None,
+            DependencyKind::SbtPlugin => Some("sbt plugin"),
+            DependencyKind::CompilerPlugin => Some("compiler plugin"),
+            DependencyKind::Override => Some("override"),
+            DependencyKind::Toolchain => Some("toolchain"),
+        }
+    }
+}
+
+/// How an artifact name is suffixed, as given by the `%` operator arity in sbt
+/// or the `:` separators in Mill and scala-cli.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CrossVersion {
+    /// `%`: a plain Java artifact, without a suffix.
+    Disabled,
+    /// `%%`: suffixed with the Scala binary version, e.g. `zio_3`.
+    #[default]
+    Binary,
+    /// `%%%`: suffixed with the platform and the Scala binary version, e.g. `zio_sjs1_3`.
+    Platform,
+    /// `cross CrossVersion.full`, or `:::` in Mill and scala-cli: suffixed with the
+    /// full Scala version, e.g. `kind-projector_2.13.14`.
+    Full,
+    /// `.cross(CrossVersion.for3Use2_13)`: the Scala 2.13 artifact, on Scala 3 as well.
+    /// With `%%%`, the platform is part of the suffix too.
+    For3Use2_13 { platform: bool },
+    /// `.cross(CrossVersion.for2_13Use3)`: the Scala 3 artifact, on Scala 2.13 as well.
+    For2_13Use3 { platform: bool },
+}
+
+impl CrossVersion {
+    /// Parses an sbt `%`, `%%` or `%%%` operator.
+    pub fn from_operator(operator: &str) -> Option<CrossVersion> {
+        match operator {
+            "%" => Some(CrossVersion::Disabled),
+            "%%" => Some(CrossVersion::Binary),
+            "%%%" => Some(CrossVersion::Platform),
+            _ => None,
+        }
+    }
+
+    /// Applies a `.cross(...)` setting such as `CrossVersion.for3Use2_13`, keeping
+    /// the platform of a `%%%` dependency. `None` for settings that aren't understood.
+    pub fn with_setting(self, setting: &str) -> Option<CrossVersion> {
+        let platform = self.is_platform();
+        match setting.trim().strip_prefix("CrossVersion.")? {
+            "disabled" => Some(CrossVersion::Disabled),
+            "binary" if platform => Some(CrossVersion::Platform),
+            "binary" => Some(CrossVersion::Binary),
+            "full" | "patch" => Some(CrossVersion::Full),
+            "for3Use2_13" => Some(CrossVersion::For3Use2_13 { platform }),
+            "for2_13Use3" => Some(CrossVersion::For2_13Use3 { platform }),
+            _ => None,
+        }
+    }
+
+    /// Whether the suffix includes the platform, as with `%%%`.
+    pub fn is_platform(self) -> bool {
+        matches!(
+            self,
+            CrossVersion::Platform
+                | CrossVersion::For3Use2_13 { platform: true }
+                | CrossVersion::For2_13Use3 { platform: true }
+        )
+    }
+}
+
+/// A platform a Scala build can be compiled for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Platform {
+    Jvm,
+    Js,
+    Native,
+}
+
+/// The versions a build targets, which determine which published artifacts apply to it.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BuildTarget {
+    /// Every Scala version the build is compiled with, e.g. its `crossScalaVersions`.
+    /// Empty when unknown.
+    pub scala_versions: Vec<Version>,
+    pub sbt_version: Option<Version>,
+    /// The platforms `%%%` dependencies are resolved for. Empty means JVM only.
+    pub platforms: Vec<Platform>,
+    /// The Scala Native version, as given by the `sbt-scala-native` plugin.
+    pub scala_native_version: Option<Version>,
+}
+
+impl BuildTarget {
+    pub fn new(scala_versions: Vec<Version>) -> Self {
+        BuildTarget {
+            scala_versions,
+            ..Default::default()
+        }
+    }
+}
+
+/// A Maven-layout repository that artifacts are resolved from.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Repository {
+    pub name: String,
+    /// The root URL of the repository, without a trailing slash.
+    pub url: String,
+}
+
+impl Repository {
+    pub fn new(name: &str, url: &str) -> Self {
+        Repository {
+            name: name.to_string(),
+            url: url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub fn maven_central() -> Self {
+        Repository::new("Maven Central", "https://repo1.maven.org/maven2")
+    }
+
+    /// The URL of the directory holding every version of the artifact.
+    pub fn artifact_url(&self, group: &Group, artifact: &Artifact) -> String {
+        format!(
+            "{}/{}/{}",
+            self.url,
+            group.value.replace('.', "/"),
+            artifact.value
+        )
+    }
+}
@@ -0,0 +1,145 @@
+use std::{
+    fmt::Display,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// When a version was published, in seconds since the Unix epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ReleaseDate(pub u64);
+
+impl ReleaseDate {
+    pub fn now() -> ReleaseDate {
+        ReleaseDate(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Parses `yyyyMMddHHmmss` in UTC, the format of `lastUpdated` in `maven-metadata.xml`.
+    pub fn parse_maven_timestamp(timestamp: &str) -> Option<ReleaseDate> {
+        let timestamp = timestamp.trim();
+        if timestamp.len() != 14 || !timestamp.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let field = |range: std::ops::Range<usize>| timestamp[range].parse::<u64>().ok();
+        ReleaseDate::from_parts(
+            field(0..4)?,
+            field(4..6)?,
+            field(6..8)?,
+            (field(8..10)?, field(10..12)?, field(12..14)?),
+        )
+    }
+
+    /// Parses an HTTP date such as `Tue, 07 May 2024 12:00:00 GMT`, the format of
+    /// the `Last-Modified` header.
+    pub fn parse_http_date(date: &str) -> Option<ReleaseDate> {
+        let mut parts = date.split_whitespace().skip(1);
+        let day = parts.next()?.parse().ok()?;
+        let month_name = parts.next()?;
+        let month = MONTHS.iter().position(|month| *month == month_name)? as u64 + 1;
+        let year = parts.next()?.parse().ok()?;
+        let mut time = parts.next()?.split(':').map(|part| part.parse().ok());
+        let time = (time.next()??, time.next()??, time.next()??);
+        ReleaseDate::from_parts(year, month, day, time)
+    }
+
+    fn from_parts(
+        year: u64,
+        month: u64,
+        day: u64,
+        (hour, minute, second): (u64, u64, u64),
+    ) -> Option<ReleaseDate> {
+        if !(1970..=9999).contains(&year) || !(1..=12).contains(&month) || !(1..=31).contains(&day)
+        {
+            return None;
+        }
+        if hour > 23 || minute > 59 || second > 60 {
+            return None;
+        }
+        let days = days_from_civil(year, month, day);
+        Some(ReleaseDate(
+            days * SECONDS_PER_DAY + hour * 3600 + minute * 60 + second,
+        ))
+    }
+
+    /// How long before `now` the version was published.
+    pub fn age(&self, now: ReleaseDate) -> Duration {
+        Duration::from_secs(now.0.saturating_sub(self.0))
+    }
+}
+
+/// Formats the date as `yyyy-MM-dd`.
+impl Display for ReleaseDate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (year, month, day) = civil_from_days(self.0 / SECONDS_PER_DAY);
+        write!(f, "{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+/// Days since the Unix epoch of a date in the proleptic Gregorian calendar, after
+/// Howard Hinnant's `days_from_civil`, restricted to dates after the epoch.
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let days = days + 719468;
+    let era = days / 146097;
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_release_dates() {
+        let date = ReleaseDate::parse_maven_timestamp("20240507120000").unwrap();
+        assert_eq!(date, ReleaseDate(1715083200));
+        assert_eq!(date.to_string(), "2024-05-07");
+        assert_eq!(
+            ReleaseDate::parse_http_date("Tue, 07 May 2024 12:00:00 GMT"),
+            Some(date)
+        );
+        assert_eq!(ReleaseDate(0).to_string(), "1970-01-01");
+        assert_eq!(
+            ReleaseDate::parse_maven_timestamp("20000229000000").map(|d| d.to_string()),
+            Some("2000-02-29".to_string())
+        );
+
+        assert_eq!(ReleaseDate::parse_maven_timestamp("2024-05-07"), None);
+        assert_eq!(ReleaseDate::parse_http_date("yesterday"), None);
+
+        assert_eq!(
+            date.age(ReleaseDate(date.0 + 2 * SECONDS_PER_DAY)),
+            Duration::from_secs(2 * SECONDS_PER_DAY)
+        );
+        assert_eq!(date.age(ReleaseDate(0)), Duration::ZERO);
+    }
+}
@@ -1,8 +1,8 @@
-use std::fmt::Display;
+use std::{collections::HashMap, fmt::Display, str::FromStr, time::Duration};
 
 use itertools::Itertools;
 
-use super::Version;
+use super::{ReleaseDate, Version};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum VersionType {
@@ -23,7 +23,31 @@ impl Display for VersionType {
     }
 }
 
+impl FromStr for VersionType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "major" => Ok(VersionType::Major),
+            "minor" => Ok(VersionType::Minor),
+            "patch" => Ok(VersionType::Patch),
+            "pre-release" | "prerelease" | "pre" => Ok(VersionType::PreRelease),
+            other => Err(format!(
+                "unknown version type '{}', expected one of: major, minor, patch, pre-release",
+                other
+            )),
+        }
+    }
+}
+
 impl VersionType {
+    pub const ALL: [VersionType; 4] = [
+        VersionType::Major,
+        VersionType::Minor,
+        VersionType::Patch,
+        VersionType::PreRelease,
+    ];
+
     pub fn next(self) -> VersionType {
         use VersionType::*;
         match self {
@@ -45,6 +69,30 @@ impl VersionType {
     }
 }
 
+/// Holds back versions published less than `min_age` ago, giving broken releases
+/// time to be pulled. Versions whose release date could not be found are held
+/// back too, while those not looked up yet are let through pending the lookup.
+#[derive(Debug, Clone, Copy)]
+pub struct MinAge<'a> {
+    pub min_age: Duration,
+    /// `None` for versions whose release date could not be found.
+    pub release_dates: &'a HashMap<Version, Option<ReleaseDate>>,
+    pub now: ReleaseDate,
+}
+
+impl MinAge<'_> {
+    pub fn allows(&self, version: &Version) -> bool {
+        if self.min_age.is_zero() {
+            return true;
+        }
+        match self.release_dates.get(version) {
+            Some(Some(date)) => date.age(self.now) >= self.min_age,
+            Some(None) => false,
+            None => true,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct UpdateOptions {
     pub major: Option<Version>,
@@ -55,12 +103,44 @@ pub struct UpdateOptions {
 
 impl UpdateOptions {
     pub fn new(current: &Version, available: &[Version]) -> Option<UpdateOptions> {
+        UpdateOptions::with_min_age(current, available, None)
+    }
+
+    /// Like [`UpdateOptions::new`], skipping the versions the min age holds back.
+    pub fn with_min_age(
+        current: &Version,
+        available: &[Version],
+        min_age: Option<&MinAge>,
+    ) -> Option<UpdateOptions> {
         match current {
-            Version::SemVer { .. } => UpdateOptions::get_options_semver(current, available),
-            _ => UpdateOptions::get_options_semver(&Version::new("0.0.0"), available),
+            Version::SemVer { .. } => {
+                UpdateOptions::get_options_semver(current, available, min_age)
+            }
+            _ => UpdateOptions::get_options_semver(&Version::new("0.0.0"), available, min_age),
         }
     }
 
+    /// Every version offered, largest update first.
+    pub fn versions(&self) -> impl Iterator<Item = &Version> {
+        [&self.major, &self.minor, &self.patch, &self.pre_release]
+            .into_iter()
+            .flatten()
+    }
+
+    pub fn get(&self, version_type: VersionType) -> Option<&Version> {
+        match version_type {
+            VersionType::Major => self.major.as_ref(),
+            VersionType::Minor => self.minor.as_ref(),
+            VersionType::Patch => self.patch.as_ref(),
+            VersionType::PreRelease => self.pre_release.as_ref(),
+        }
+    }
+
+    /// Returns true if any of the given version types has an update available.
+    pub fn has_any(&self, version_types: &[VersionType]) -> bool {
+        version_types.iter().any(|vt| self.get(*vt).is_some())
+    }
+
     pub fn is_empty(&self) -> bool {
         self.major.is_none()
             && self.minor.is_none()
@@ -70,11 +150,17 @@ impl UpdateOptions {
 
     /// Given the current version, and a list of versions, this method returns the
     /// next major, minor, patch, or pre-release version, if any, that is greater
-    /// than the current version.
-    pub fn get_options_semver(current: &Version, available: &[Version]) -> Option<UpdateOptions> {
+    /// than the current version. Versions published too recently for the min age
+    /// are ignored.
+    pub fn get_options_semver(
+        current: &Version,
+        available: &[Version],
+        min_age: Option<&MinAge>,
+    ) -> Option<UpdateOptions> {
         let available: Vec<&Version> = available
             .iter()
             .filter(|v| matches!(v, Version::SemVer { .. }) && *v > current)
+            .filter(|v| min_age.is_none_or(|min_age| min_age.allows(v)))
             .sorted()
             .collect();
 
@@ -212,4 +298,48 @@ mod tests {
         assert_eq!(options.patch, Some(Version::new("2.1.1")));
         assert_eq!(options.pre_release, Some(Version::new("3.1.0-M1")));
     }
+
+    #[test]
+    fn test_get_options_with_min_age() {
+        let current = Version::new("1.2.3");
+        let available = vec![
+            Version::new("1.2.4"),
+            Version::new("1.2.5"),
+            Version::new("1.3.0"),
+        ];
+        let day = 24 * 60 * 60;
+        let now = ReleaseDate(100 * day);
+        let release_dates = HashMap::from([
+            (Version::new("1.2.4"), Some(ReleaseDate(90 * day))),
+            (Version::new("1.2.5"), Some(ReleaseDate(100 * day - 3600))),
+            (Version::new("1.3.0"), None),
+        ]);
+        let available = [available, vec![Version::new("1.4.0")]].concat();
+        let min_age = MinAge {
+            min_age: Duration::from_secs(day),
+            release_dates: &release_dates,
+            now,
+        };
+
+        let options = UpdateOptions::with_min_age(&current, &available, Some(&min_age)).unwrap();
+
+        // 1.3.0 has no known date, while 1.4.0 has not been looked up yet
+        assert_eq!(options.minor, Some(Version::new("1.4.0")));
+        assert_eq!(options.patch, Some(Version::new("1.2.4")));
+        assert!(!min_age.allows(&Version::new("1.3.0")));
+        assert!(MinAge {
+            min_age: Duration::ZERO,
+            ..min_age
+        }
+        .allows(&Version::new("1.3.0")));
+    }
+
+    #[test]
+    fn test_version_type_from_str() {
+        assert_eq!("major".parse(), Ok(VersionType::Major));
+        assert_eq!(" Minor ".parse(), Ok(VersionType::Minor));
+        assert_eq!("patch".parse(), Ok(VersionType::Patch));
+        assert_eq!("pre-release".parse(), Ok(VersionType::PreRelease));
+        assert!("huge".parse::<VersionType>().is_err());
+    }
 }
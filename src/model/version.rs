@@ -97,6 +97,11 @@ impl Version {
         }
     }
 
+    /// The `major.minor` line of the version, e.g. `2.13` for `2.13.12`.
+    pub fn line(&self) -> Option<String> {
+        Some(format!("{}.{}", self.major()?, self.minor()?))
+    }
+
     pub fn patch(&self) -> Option<u32> {
         match self {
             Version::SemVer { patch, .. } => Some(*patch),
@@ -0,0 +1,137 @@
+use std::{
+    env, fs,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::model::{Artifact, Group, Repository, Version};
+
+/// A version list stored on disk, along with when it was fetched.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedVersions {
+    pub versions: Vec<String>,
+    /// Seconds since the Unix epoch.
+    pub fetched_at: u64,
+}
+
+impl CachedVersions {
+    pub fn versions(&self) -> Vec<Version> {
+        self.versions.iter().map(|v| Version::new(v)).collect()
+    }
+
+    pub fn is_fresh(&self, ttl: Duration) -> bool {
+        now().saturating_sub(self.fetched_at) < ttl.as_secs()
+    }
+}
+
+/// Caches version lists on disk, keyed by repository, group and artifact.
+#[derive(Debug, Clone)]
+pub struct VersionCache {
+    dir: PathBuf,
+    pub ttl: Duration,
+}
+
+impl VersionCache {
+    pub fn new(dir: PathBuf, ttl: Duration) -> Self {
+        VersionCache { dir, ttl }
+    }
+
+    /// `$XDG_CACHE_HOME/given`, or `~/.cache/given`.
+    pub fn default_dir() -> Option<PathBuf> {
+        let cache_dir = env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+        Some(cache_dir.join("given"))
+    }
+
+    fn path(&self, repository: &Repository, group: &Group, artifact: &Artifact) -> PathBuf {
+        let repository_dir: String = repository
+            .url
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '.' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.dir
+            .join("versions")
+            .join(repository_dir)
+            .join(&group.value)
+            .join(format!("{}.json", artifact.value))
+    }
+
+    /// The cached entry, whether or not it has expired.
+    pub fn get(
+        &self,
+        repository: &Repository,
+        group: &Group,
+        artifact: &Artifact,
+    ) -> Option<CachedVersions> {
+        let json = fs::read_to_string(self.path(repository, group, artifact)).ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    pub fn put(
+        &self,
+        repository: &Repository,
+        group: &Group,
+        artifact: &Artifact,
+        versions: &[Version],
+    ) -> Result<()> {
+        let path = self.path(repository, group, artifact);
+        let cached = CachedVersions {
+            versions: versions.iter().map(|v| v.to_string()).collect(),
+            fetched_at: now(),
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        fs::write(&path, serde_json::to_string(&cached)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_version_cache() -> Result<()> {
+        let dir = tempdir()?;
+        let cache = VersionCache::new(dir.path().to_path_buf(), Duration::from_secs(3600));
+        let repository = Repository::maven_central();
+        let group = Group::new("dev.zio");
+        let artifact = Artifact::new("zio_3");
+
+        assert_eq!(cache.get(&repository, &group, &artifact), None);
+
+        let versions = vec![Version::new("2.0.0"), Version::new("2.0.1")];
+        cache.put(&repository, &group, &artifact, &versions)?;
+
+        let cached = cache.get(&repository, &group, &artifact).unwrap();
+        assert_eq!(cached.versions(), versions);
+        assert!(cached.is_fresh(cache.ttl));
+        assert!(!cached.is_fresh(Duration::ZERO));
+
+        let other = Repository::new("Internal", "https://maven.example.com");
+        assert_eq!(cache.get(&other, &group, &artifact), None);
+
+        Ok(())
+    }
+}
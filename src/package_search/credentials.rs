@@ -0,0 +1,215 @@
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use reqwest::{RequestBuilder, Url};
+
+/// How to authenticate against a repository.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Auth {
+    Basic { user: String, password: String },
+    Bearer { token: String },
+}
+
+/// Credentials for every repository on a host.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Credentials {
+    pub host: String,
+    pub auth: Auth,
+}
+
+impl Credentials {
+    /// Collects credentials from, in order of precedence:
+    /// - the `GIVEN_REPOSITORY_HOST` environment variable, along with either
+    ///   `GIVEN_REPOSITORY_TOKEN` or `GIVEN_REPOSITORY_USER` and `GIVEN_REPOSITORY_PASSWORD`
+    /// - sbt credentials files: `$SBT_CREDENTIALS`, `~/.sbt/.credentials` and `~/.ivy2/.credentials`
+    /// - coursier's `credentials.properties`, or `$COURSIER_CREDENTIALS`
+    pub fn load() -> Vec<Credentials> {
+        let mut credentials: Vec<Credentials> =
+            from_env(|key| env::var(key).ok()).into_iter().collect();
+
+        let home = env::var_os("HOME").map(PathBuf::from);
+        let mut sbt_files: Vec<PathBuf> = env::var_os("SBT_CREDENTIALS")
+            .map(PathBuf::from)
+            .into_iter()
+            .collect();
+        if let Some(home) = &home {
+            sbt_files.push(home.join(".sbt/.credentials"));
+            sbt_files.push(home.join(".ivy2/.credentials"));
+        }
+        for path in sbt_files {
+            if let Some(code) = read(&path) {
+                credentials.extend(parse_sbt_credentials(&code));
+            }
+        }
+
+        match env::var("COURSIER_CREDENTIALS") {
+            // either a path, or the credentials themselves
+            Ok(value) => {
+                let code = read(Path::new(&value)).unwrap_or(value);
+                credentials.extend(parse_coursier_credentials(&code));
+            }
+            Err(_) => {
+                let config_dir = env::var_os("XDG_CONFIG_HOME")
+                    .map(PathBuf::from)
+                    .or_else(|| home.as_ref().map(|home| home.join(".config")));
+                let paths = [
+                    config_dir.map(|dir| dir.join("coursier/credentials.properties")),
+                    home.map(|home| {
+                        home.join("Library/Application Support/Coursier/credentials.properties")
+                    }),
+                ];
+                for path in paths.into_iter().flatten() {
+                    if let Some(code) = read(&path) {
+                        credentials.extend(parse_coursier_credentials(&code));
+                    }
+                }
+            }
+        }
+
+        credentials
+    }
+
+    /// Attaches the first credentials matching the host of `url`, if any.
+    pub fn authenticate(
+        credentials: &[Credentials],
+        url: &str,
+        request: RequestBuilder,
+    ) -> RequestBuilder {
+        let Some(host) = Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(String::from))
+        else {
+            return request;
+        };
+        match credentials.iter().find(|c| c.host == host).map(|c| &c.auth) {
+            Some(Auth::Basic { user, password }) => request.basic_auth(user, Some(password)),
+            Some(Auth::Bearer { token }) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+}
+
+fn read(path: &Path) -> Option<String> {
+    path.is_file()
+        .then(|| fs::read_to_string(path).ok())
+        .flatten()
+}
+
+fn from_env(var: impl Fn(&str) -> Option<String>) -> Option<Credentials> {
+    let host = var("GIVEN_REPOSITORY_HOST")?;
+    let auth = match var("GIVEN_REPOSITORY_TOKEN") {
+        Some(token) => Auth::Bearer { token },
+        None => Auth::Basic {
+            user: var("GIVEN_REPOSITORY_USER")?,
+            password: var("GIVEN_REPOSITORY_PASSWORD")?,
+        },
+    };
+    Some(Credentials { host, auth })
+}
+
+/// Parses `key=value` lines, skipping blank lines and `#` comments.
+fn parse_properties(code: &str) -> Vec<(&str, &str)> {
+    code.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('!'))
+        .filter_map(|line| {
+            let (key, value) = line.split_once(['=', ':'])?;
+            Some((key.trim(), value.trim()))
+        })
+        .collect()
+}
+
+/// Parses an sbt credentials file, which holds a single `realm`, `host`,
+/// `user` and `password`.
+pub fn parse_sbt_credentials(code: &str) -> Option<Credentials> {
+    let properties: BTreeMap<_, _> = parse_properties(code).into_iter().collect();
+    Some(Credentials {
+        host: properties.get("host")?.to_string(),
+        auth: Auth::Basic {
+            user: properties.get("user")?.to_string(),
+            password: properties.get("password")?.to_string(),
+        },
+    })
+}
+
+/// Parses coursier's `credentials.properties`, where each entry is prefixed
+/// with a name, e.g. `internal.host`, `internal.username` and `internal.password`.
+pub fn parse_coursier_credentials(code: &str) -> Vec<Credentials> {
+    let mut entries: BTreeMap<&str, BTreeMap<&str, &str>> = BTreeMap::new();
+    for (key, value) in parse_properties(code) {
+        if let Some((prefix, property)) = key.rsplit_once('.') {
+            entries.entry(prefix).or_default().insert(property, value);
+        }
+    }
+
+    entries
+        .values()
+        .filter_map(|entry| {
+            Some(Credentials {
+                host: entry.get("host")?.to_string(),
+                auth: Auth::Basic {
+                    user: entry.get("username")?.to_string(),
+                    password: entry.get("password")?.to_string(),
+                },
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_credentials() {
+        let sbt =
+            "realm=Artifactory Realm\nhost=artifactory.example.com\nuser=kit\npassword=hunter2\n";
+        assert_eq!(
+            parse_sbt_credentials(sbt),
+            Some(Credentials {
+                host: "artifactory.example.com".to_string(),
+                auth: Auth::Basic {
+                    user: "kit".to_string(),
+                    password: "hunter2".to_string(),
+                },
+            })
+        );
+
+        let coursier = r#"
+# internal repository
+internal.host=maven.example.com
+internal.username=kit
+internal.password=s3cr=t
+other.host=incomplete.example.com
+"#;
+        assert_eq!(
+            parse_coursier_credentials(coursier),
+            vec![Credentials {
+                host: "maven.example.com".to_string(),
+                auth: Auth::Basic {
+                    user: "kit".to_string(),
+                    password: "s3cr=t".to_string(),
+                },
+            }]
+        );
+
+        let env = |key: &str| match key {
+            "GIVEN_REPOSITORY_HOST" => Some("maven.pkg.github.com".to_string()),
+            "GIVEN_REPOSITORY_TOKEN" => Some("ghp_token".to_string()),
+            _ => None,
+        };
+        assert_eq!(
+            from_env(env),
+            Some(Credentials {
+                host: "maven.pkg.github.com".to_string(),
+                auth: Auth::Bearer {
+                    token: "ghp_token".to_string()
+                },
+            })
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use std::{env, fs, path::Path};
+
+use anyhow::{Context, Result};
+use reqwest::{Certificate, Client, NoProxy, Proxy};
+
+use crate::config::Config;
+
+/// The environment variables through which reqwest already picks up a proxy,
+/// along with `NO_PROXY`.
+const PROXY_VARIABLES: [&str; 6] = [
+    "HTTPS_PROXY",
+    "https_proxy",
+    "HTTP_PROXY",
+    "http_proxy",
+    "ALL_PROXY",
+    "all_proxy",
+];
+
+/// A proxy configured the way the JVM expects it, e.g.
+/// `-Dhttps.proxyHost=proxy.example.com -Dhttps.proxyPort=3128`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JvmProxy {
+    pub host: String,
+    pub port: u16,
+    pub user: Option<String>,
+    pub password: Option<String>,
+    /// From `-Dhttp.nonProxyHosts`, e.g. `localhost|*.example.com`.
+    pub non_proxy_hosts: Vec<String>,
+}
+
+impl JvmProxy {
+    /// Reads the `https.proxy*` system properties from JVM options, falling back
+    /// to the `http.proxy*` ones.
+    pub fn parse(options: &str) -> Option<JvmProxy> {
+        let properties: Vec<(&str, &str)> = options
+            .split_whitespace()
+            .filter_map(|option| option.trim_matches(['"', '\'']).strip_prefix("-D"))
+            .filter_map(|property| property.split_once('='))
+            .map(|(key, value)| (key, value.trim_matches(['"', '\''])))
+            .collect();
+        let property = |key: &str| {
+            properties
+                .iter()
+                .rev()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| v.to_string())
+        };
+
+        let scheme = ["https", "http"]
+            .into_iter()
+            .find(|scheme| property(&format!("{}.proxyHost", scheme)).is_some())?;
+        let default_port = if scheme == "https" { 443 } else { 80 };
+        Some(JvmProxy {
+            host: property(&format!("{}.proxyHost", scheme))?,
+            port: property(&format!("{}.proxyPort", scheme))
+                .and_then(|port| port.parse().ok())
+                .unwrap_or(default_port),
+            user: property(&format!("{}.proxyUser", scheme)),
+            password: property(&format!("{}.proxyPassword", scheme)),
+            non_proxy_hosts: property("http.nonProxyHosts")
+                .map(|hosts| {
+                    hosts
+                        .split('|')
+                        .map(|host| host.trim().to_string())
+                        .filter(|host| !host.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        })
+    }
+
+    /// The proxy set in `SBT_OPTS`, or in the project's `.jvmopts`.
+    pub fn discover(project_path: &Path) -> Option<JvmProxy> {
+        env::var("SBT_OPTS")
+            .ok()
+            .and_then(|options| JvmProxy::parse(&options))
+            .or_else(|| {
+                let options = fs::read_to_string(project_path.join(".jvmopts")).ok()?;
+                JvmProxy::parse(&options)
+            })
+    }
+
+    fn proxy(&self) -> Result<Proxy> {
+        let mut proxy = Proxy::all(format!("http://{}:{}", self.host, self.port))
+            .with_context(|| format!("Invalid proxy {}:{}", self.host, self.port))?;
+        if let Some(user) = &self.user {
+            proxy = proxy.basic_auth(user, self.password.as_deref().unwrap_or_default());
+        }
+        // The JVM's `*.example.com` is reqwest's `.example.com`.
+        let no_proxy = self
+            .non_proxy_hosts
+            .iter()
+            .map(|host| host.trim_start_matches('*'))
+            .collect::<Vec<_>>()
+            .join(",");
+        Ok(proxy.no_proxy(NoProxy::from_string(&no_proxy)))
+    }
+}
+
+/// Builds the HTTP client used for every repository request. Proxies come from
+/// `HTTPS_PROXY` and `NO_PROXY` when set, and from the JVM options sbt is started
+/// with otherwise; the config's `ca-certificates` are trusted on top of the system's.
+pub fn client(config: &Config, project_path: &Path) -> Result<Client> {
+    let mut builder = Client::builder();
+
+    let has_env_proxy = PROXY_VARIABLES
+        .iter()
+        .any(|variable| env::var_os(variable).is_some_and(|value| !value.is_empty()));
+    if !has_env_proxy {
+        if let Some(jvm_proxy) = JvmProxy::discover(project_path) {
+            builder = builder.proxy(jvm_proxy.proxy()?);
+        }
+    }
+
+    for path in &config.ca_certificates {
+        let pem = fs::read(path)
+            .with_context(|| format!("Failed to read CA certificate {}", path.display()))?;
+        let certificates = Certificate::from_pem_bundle(&pem)
+            .with_context(|| format!("Invalid CA certificate {}", path.display()))?;
+        for certificate in certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+
+    builder.build().context("Failed to build the HTTP client")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_jvm_proxy() {
+        let options = r#"
+-Xmx2G
+-Dhttps.proxyHost=proxy.example.com
+-Dhttps.proxyPort=3128
+-Dhttps.proxyUser=alice
+"-Dhttp.nonProxyHosts=localhost|*.internal.example.com"
+"#;
+        assert_eq!(
+            JvmProxy::parse(options),
+            Some(JvmProxy {
+                host: "proxy.example.com".to_string(),
+                port: 3128,
+                user: Some("alice".to_string()),
+                password: None,
+                non_proxy_hosts: vec![
+                    "localhost".to_string(),
+                    "*.internal.example.com".to_string()
+                ],
+            })
+        );
+
+        let http_only = JvmProxy::parse("-Dhttp.proxyHost=proxy -Dsbt.log.noformat=true").unwrap();
+        assert_eq!((http_only.host.as_str(), http_only.port), ("proxy", 80));
+
+        assert_eq!(JvmProxy::parse("-Xmx2G -Dfile.encoding=UTF-8"), None);
+    }
+}
@@ -0,0 +1,246 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use super::{metadata::MavenMetadata, PackageSearch};
+use crate::model::{Artifact, Group, Repository, Version};
+
+/// How a local directory lays out its artifacts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// `dev/zio/zio_3/2.0.0/`, as used by `~/.m2/repository` and the coursier cache.
+    Maven,
+    /// `dev.zio/zio_3/2.0.0/`, as used by `~/.ivy2/local`.
+    Ivy,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalRepository {
+    pub path: PathBuf,
+    pub layout: Layout,
+}
+
+impl LocalRepository {
+    pub fn new(path: PathBuf, layout: Layout) -> Self {
+        LocalRepository { path, layout }
+    }
+
+    fn group_dir(&self, group: &Group) -> PathBuf {
+        match self.layout {
+            Layout::Maven => self.path.join(group.value.replace('.', "/")),
+            Layout::Ivy => self.path.join(&group.value),
+        }
+    }
+
+    /// Every version directory of the artifact, along with any versions listed in
+    /// the `maven-metadata*.xml` files next to them.
+    fn versions(&self, group: &Group, artifact: &Artifact) -> Vec<Version> {
+        let artifact_dir = self.group_dir(group).join(&artifact.value);
+        let Ok(entries) = fs::read_dir(&artifact_dir) else {
+            return vec![];
+        };
+
+        let mut versions = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if path.is_dir() && has_files(&path) {
+                versions.push(Version::new(&name));
+            } else if name.starts_with("maven-metadata") && name.ends_with(".xml") {
+                let metadata = fs::read_to_string(&path)
+                    .ok()
+                    .and_then(|xml| MavenMetadata::parse(&xml).ok());
+                versions.extend(metadata.into_iter().flat_map(|m| m.versions));
+            }
+        }
+        versions
+    }
+}
+
+/// Whether the version directory holds anything, as coursier leaves empty
+/// directories behind for failed downloads.
+fn has_files(path: &Path) -> bool {
+    fs::read_dir(path).is_ok_and(|mut entries| entries.next().is_some())
+}
+
+/// Searches the local caches of sbt, Maven and coursier, which already hold the
+/// metadata and POMs of everything resolved before, along with locally published builds.
+#[derive(Debug, Clone, Default)]
+pub struct LocalPackageSearch {
+    repositories: Vec<LocalRepository>,
+    coursier_cache: Option<PathBuf>,
+}
+
+impl LocalPackageSearch {
+    pub fn new(repositories: Vec<LocalRepository>) -> Self {
+        LocalPackageSearch {
+            repositories,
+            coursier_cache: None,
+        }
+    }
+
+    /// `~/.m2/repository`, `~/.ivy2/local` and the coursier cache of each remote repository.
+    pub fn discover(remote_repositories: &[Repository]) -> Self {
+        let home = env::var_os("HOME").map(PathBuf::from);
+        let mut repositories = Vec::new();
+        if let Some(home) = &home {
+            repositories.push(LocalRepository::new(
+                home.join(".m2/repository"),
+                Layout::Maven,
+            ));
+            repositories.push(LocalRepository::new(home.join(".ivy2/local"), Layout::Ivy));
+        }
+
+        let coursier_cache = env::var_os("COURSIER_CACHE")
+            .map(PathBuf::from)
+            .or_else(|| {
+                env::var_os("XDG_CACHE_HOME").map(|dir| PathBuf::from(dir).join("coursier/v1"))
+            })
+            .or_else(|| {
+                let home = home.as_ref()?;
+                [".cache/coursier/v1", "Library/Caches/Coursier/v1"]
+                    .into_iter()
+                    .map(|dir| home.join(dir))
+                    .find(|dir| dir.is_dir())
+            });
+
+        LocalPackageSearch {
+            repositories: repositories
+                .into_iter()
+                .filter(|repository| repository.path.is_dir())
+                .collect(),
+            coursier_cache,
+        }
+        .with_repositories(remote_repositories)
+    }
+
+    /// Adds the coursier cache directories of the given remote repositories,
+    /// e.g. `~/.cache/coursier/v1/https/repo1.maven.org/maven2`.
+    pub fn with_repositories(&self, remote_repositories: &[Repository]) -> Self {
+        let mut search = self.clone();
+        let Some(coursier_cache) = &self.coursier_cache else {
+            return search;
+        };
+        for repository in remote_repositories {
+            let Some((scheme, rest)) = repository.url.split_once("://") else {
+                continue;
+            };
+            let local = LocalRepository::new(coursier_cache.join(scheme).join(rest), Layout::Maven);
+            if local.path.is_dir() && !search.repositories.contains(&local) {
+                search.repositories.push(local);
+            }
+        }
+        search
+    }
+}
+
+#[async_trait]
+impl PackageSearch for LocalPackageSearch {
+    async fn search_artifacts(
+        &self,
+        group: &Group,
+        artifact_prefix: &str,
+    ) -> Result<Vec<Artifact>> {
+        let mut artifacts = Vec::new();
+        for repository in &self.repositories {
+            let Ok(entries) = fs::read_dir(repository.group_dir(group)) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let artifact = Artifact::new(&name);
+                if entry.path().is_dir()
+                    && name.starts_with(artifact_prefix)
+                    && !artifacts.contains(&artifact)
+                {
+                    artifacts.push(artifact);
+                }
+            }
+        }
+        artifacts.sort();
+        Ok(artifacts)
+    }
+
+    /// The versions found in every local repository. Artifacts that were never
+    /// resolved locally simply have no versions.
+    async fn get_versions(&self, group: &Group, artifact: &Artifact) -> Result<Vec<Version>> {
+        let mut versions = Vec::new();
+        for repository in &self.repositories {
+            for version in repository.versions(group, artifact) {
+                if !versions.contains(&version) {
+                    versions.push(version);
+                }
+            }
+        }
+        Ok(versions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    fn touch(path: PathBuf) -> std::io::Result<()> {
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, "")
+    }
+
+    #[tokio::test]
+    async fn test_local_package_search() -> Result<()> {
+        let dir = tempdir()?;
+        let m2 = dir.path().join("m2");
+        let ivy = dir.path().join("ivy");
+        let coursier = dir.path().join("coursier");
+
+        touch(m2.join("dev/zio/zio_3/2.0.0/zio_3-2.0.0.pom"))?;
+        fs::create_dir_all(m2.join("dev/zio/zio_3/2.0.1"))?;
+        touch(ivy.join("dev.zio/zio_3/2.1.0-SNAPSHOT/ivys/ivy.xml"))?;
+        let coursier_central = coursier.join("https/repo1.maven.org/maven2");
+        touch(coursier_central.join("dev/zio/zio_3/2.0.2/zio_3-2.0.2.pom"))?;
+        fs::write(
+            coursier_central.join("dev/zio/zio_3/maven-metadata.xml"),
+            "<metadata><versioning><versions><version>2.0.3</version></versions></versioning></metadata>",
+        )?;
+
+        let search = LocalPackageSearch {
+            repositories: vec![
+                LocalRepository::new(m2, Layout::Maven),
+                LocalRepository::new(ivy, Layout::Ivy),
+            ],
+            coursier_cache: Some(coursier),
+        }
+        .with_repositories(&[Repository::maven_central()]);
+
+        let group = Group::new("dev.zio");
+        let mut versions = search.get_versions(&group, &Artifact::new("zio_3")).await?;
+        versions.sort();
+        assert_eq!(
+            versions,
+            vec![
+                Version::new("2.0.0"),
+                Version::new("2.0.2"),
+                Version::new("2.0.3"),
+                Version::new("2.1.0-SNAPSHOT"),
+            ]
+        );
+
+        assert_eq!(
+            search.search_artifacts(&group, "zio").await?,
+            vec![Artifact::new("zio_3")]
+        );
+        assert_eq!(
+            search
+                .get_versions(&group, &Artifact::new("zio_2.13"))
+                .await?,
+            vec![]
+        );
+
+        Ok(())
+    }
+}
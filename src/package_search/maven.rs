@@ -1,16 +1,58 @@
-use crate::model::{Artifact, Group, Version};
+use crate::{
+    config::Config,
+    model::{
+        Artifact, BuildTarget, CrossVersion, DependencyKind, Group, Migration, ReleaseDate,
+        Relocation, Repository, Version,
+    },
+};
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use quick_xml::de::from_str;
-use reqwest::Client;
+use reqwest::{Client, Method, Response};
 use scraper::{Html, Selector};
-use serde::Deserialize;
-
-use super::PackageSearch;
-
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::sync::Semaphore;
+
+use super::{
+    artifact_candidates,
+    cache::VersionCache,
+    credentials::Credentials,
+    http,
+    local::LocalPackageSearch,
+    metadata::MavenMetadata,
+    pom::{Pom, ProjectLinks},
+    retry::{self, RetryPolicy},
+    PackageSearch, PackageSearchExt,
+};
+
+type ArtifactVersion = (Group, Artifact, Version);
+
+/// Searches a chain of Maven-layout repositories, Maven Central first.
+#[derive(Clone)]
 pub struct MavenPackageSearch {
     client: Client,
+    repositories: Vec<Repository>,
+    credentials: Vec<Credentials>,
+    cache: Option<VersionCache>,
+    /// The local caches of sbt, Maven and coursier, searched before the network.
+    local: Option<LocalPackageSearch>,
+    /// Answer only from the cache, without touching the network.
+    offline: bool,
+    /// The artifacts whose versions came from an expired cache entry.
+    stale: Arc<Mutex<HashSet<(Group, Artifact)>>>,
+    /// Caps the number of requests in flight, shared by every clone.
+    requests: Arc<Semaphore>,
+    /// Release dates learned along the way, e.g. from `maven-metadata.xml`.
+    release_dates: Arc<Mutex<HashMap<ArtifactVersion, ReleaseDate>>>,
+    retry_policy: RetryPolicy,
+    /// Artifacts known to have moved, checked before looking for relocations in POMs.
+    migrations: Vec<Migration>,
 }
 
 impl Default for MavenPackageSearch {
@@ -23,67 +65,519 @@ impl MavenPackageSearch {
     pub fn new() -> Self {
         MavenPackageSearch {
             client: Client::new(),
+            repositories: vec![Repository::maven_central()],
+            credentials: Vec::new(),
+            cache: None,
+            local: None,
+            offline: false,
+            stale: Arc::default(),
+            requests: Arc::new(Semaphore::new(Config::default().max_concurrent_requests)),
+            release_dates: Arc::default(),
+            retry_policy: RetryPolicy::default(),
+            migrations: Migration::bundled(),
         }
     }
 
-    async fn fetch_url(&self, url: &str) -> Result<String> {
-        let response = self
-            .client
-            .get(url)
-            .header("User-Agent", "Rust reqwest client")
-            .send()
-            .await
-            .context("Failed to send request to Maven Central")?;
+    /// Maven Central, followed by the repositories from the user's config,
+    /// authenticated with the user's credentials and cached on disk. Requests go
+    /// through the proxy configured for the project at `project_path`, if any.
+    pub fn from_config(config: &Config, project_path: &Path) -> Result<Self> {
+        let ttl = Duration::from_secs(config.cache_ttl_hours * 60 * 60);
+        Ok(MavenPackageSearch {
+            client: http::client(config, project_path)?,
+            credentials: Credentials::load(),
+            cache: VersionCache::default_dir().map(|dir| VersionCache::new(dir, ttl)),
+            local: Some(LocalPackageSearch::discover(&[Repository::maven_central()])),
+            requests: Arc::new(Semaphore::new(config.max_concurrent_requests.max(1))),
+            retry_policy: RetryPolicy {
+                max_retries: config.max_retries,
+                ..RetryPolicy::default()
+            },
+            migrations: config.migrations(),
+            ..MavenPackageSearch::new()
+        }
+        .with_repositories(&config.repositories))
+    }
+
+    pub fn with_cache(mut self, cache: VersionCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
 
-        if !response.status().is_success() {
-            anyhow::bail!("Request failed with status: {}", response.status());
+    /// Only answer from the cache, serving expired entries as stale.
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Appends repositories to the chain, skipping any that are already in it.
+    pub fn with_repositories(&self, repositories: &[Repository]) -> Self {
+        let mut search = self.clone();
+        for repository in repositories {
+            if !search.repositories.iter().any(|r| r.url == repository.url) {
+                search.repositories.push(repository.clone());
+            }
         }
+        search.local = self
+            .local
+            .as_ref()
+            .map(|local| local.with_repositories(repositories));
+        search
+    }
+
+    pub fn with_local(mut self, local: LocalPackageSearch) -> Self {
+        self.local = Some(local);
+        self
+    }
+
+    pub fn repositories(&self) -> &[Repository] {
+        &self.repositories
+    }
+
+    /// The HTTP client, with the user's proxy and certificates configured.
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
 
-        response
+    /// Fetches the body of `url`.
+    async fn fetch_url(&self, url: &str) -> Result<String> {
+        self.send(Method::GET, url)
+            .await?
             .text()
             .await
             .context("Failed to read response body")
     }
 
-    pub async fn get_github_repo(
+    /// Sends a request to `url`, retrying rate-limited and failed requests
+    /// according to the retry policy.
+    async fn send(&self, method: Method, url: &str) -> Result<Response> {
+        let mut attempt = 0;
+        loop {
+            let outcome = {
+                let _permit = self.requests.acquire().await?;
+                self.send_once(method.clone(), url).await
+            };
+            match outcome {
+                Ok(response) => return Ok(response),
+                Err(FetchError::Transient { error, retry_after })
+                    if attempt < self.retry_policy.max_retries =>
+                {
+                    log::debug!("Retrying {}: {:#}", url, error);
+                    tokio::time::sleep(self.retry_policy.delay(attempt, retry_after)).await;
+                    attempt += 1;
+                }
+                Err(FetchError::Transient { error, .. }) if attempt > 0 => {
+                    return Err(error.context(format!("Gave up after {} retries", attempt)))
+                }
+                Err(FetchError::Transient { error, .. }) | Err(FetchError::Fatal(error)) => {
+                    return Err(error)
+                }
+            }
+        }
+    }
+
+    async fn send_once(
+        &self,
+        method: Method,
+        url: &str,
+    ) -> std::result::Result<Response, FetchError> {
+        let request = self
+            .client
+            .request(method, url)
+            .header("User-Agent", "Rust reqwest client");
+        let response = Credentials::authenticate(&self.credentials, url, request)
+            .send()
+            .await
+            .map_err(|err| {
+                let is_transient = err.is_timeout();
+                let error =
+                    anyhow::Error::new(err).context(format!("Failed to send request to {}", url));
+                if is_transient {
+                    FetchError::Transient {
+                        error,
+                        retry_after: None,
+                    }
+                } else {
+                    FetchError::Fatal(error)
+                }
+            })?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+            return Err(FetchError::Fatal(anyhow::anyhow!(
+                "Request failed with status: {} (check the credentials for this repository)",
+                status
+            )));
+        }
+        if RetryPolicy::is_retryable(status) {
+            return Err(FetchError::Transient {
+                error: anyhow::anyhow!("Request failed with status: {}", status),
+                retry_after: retry::retry_after(response.headers()),
+            });
+        }
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Err(FetchError::Fatal(
+                anyhow::Error::new(NotFound)
+                    .context(format!("Request failed with status: {}", status)),
+            ));
+        }
+        if !status.is_success() {
+            return Err(FetchError::Fatal(anyhow::anyhow!(
+                "Request failed with status: {}",
+                status
+            )));
+        }
+        Ok(response)
+    }
+
+    /// Fetches and parses `maven-metadata.xml` for the given artifact.
+    pub async fn get_metadata(
         &self,
+        repository: &Repository,
         group: &Group,
         artifact: &Artifact,
-        version: &Version,
-    ) -> Result<Option<String>> {
-        let artifact_search_str = format!("{}_3", artifact.value);
-        let search_results = self.search_artifacts(group, &artifact_search_str).await?;
-        let first_artifact = search_results
-            .first()
-            .ok_or_else(|| anyhow::anyhow!("No artifacts found"))?;
-
+    ) -> Result<MavenMetadata> {
         let url = format!(
-            "https://repo1.maven.org/maven2/{}/{}/{}/{}-{}.pom",
-            group.value.replace('.', "/"),
-            first_artifact.value,
-            version,
-            first_artifact.value,
-            version
+            "{}/maven-metadata.xml",
+            repository.artifact_url(group, artifact)
         );
 
         let body = self.fetch_url(&url).await?;
+        let metadata = MavenMetadata::parse(&body)?;
+        let last_released = metadata.release.as_ref().or(metadata.latest.as_ref());
+        let last_updated = metadata
+            .last_updated
+            .as_deref()
+            .and_then(ReleaseDate::parse_maven_timestamp);
+        if let (Some(version), Some(date)) = (last_released, last_updated) {
+            self.release_dates
+                .lock()
+                .unwrap()
+                .insert((group.clone(), artifact.clone(), version.clone()), date);
+        }
+        Ok(metadata)
+    }
+
+    /// Scrapes the versions from the HTML directory listing of the artifact.
+    async fn get_versions_from_listing(
+        &self,
+        repository: &Repository,
+        group: &Group,
+        artifact: &Artifact,
+    ) -> Result<Vec<Version>> {
+        let url = format!("{}/", repository.artifact_url(group, artifact));
+
+        let body = self.fetch_url(&url).await?;
+
+        let document = Html::parse_document(&body);
+        let selector = Selector::parse("a").unwrap();
+
+        let versions = document
+            .select(&selector)
+            .filter_map(|element| {
+                let href = element.value().attr("href")?;
+                if href.ends_with('/') && href != "../" {
+                    Some(Version::new(href.trim_end_matches('/')))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        Ok(versions)
+    }
+
+    /// Reads the versions from `maven-metadata.xml`, falling back to the HTML
+    /// directory listing for repositories that don't serve metadata. An artifact
+    /// the repository doesn't have has no versions.
+    async fn get_versions_from(
+        &self,
+        repository: &Repository,
+        group: &Group,
+        artifact: &Artifact,
+    ) -> Result<Vec<Version>> {
+        match self.get_metadata(repository, group, artifact).await {
+            Ok(metadata) => Ok(metadata.versions),
+            Err(metadata_err) => {
+                match self
+                    .get_versions_from_listing(repository, group, artifact)
+                    .await
+                {
+                    Err(err) if is_not_found(&metadata_err) && is_not_found(&err) => Ok(vec![]),
+                    listing => listing.with_context(|| {
+                        format!("No usable maven-metadata.xml: {:#}", metadata_err)
+                    }),
+                }
+            }
+        }
+    }
+
+    /// Answers from the cache while it is fresh, and otherwise fetches and caches the
+    /// versions. Expired entries are still used when offline or when the fetch fails,
+    /// in which case the artifact is marked as stale.
+    async fn get_cached_versions_from(
+        &self,
+        repository: &Repository,
+        group: &Group,
+        artifact: &Artifact,
+    ) -> Result<Vec<Version>> {
+        let Some(cache) = &self.cache else {
+            if self.offline {
+                anyhow::bail!("{}:{} is not cached", group, artifact);
+            }
+            return self.get_versions_from(repository, group, artifact).await;
+        };
+
+        let cached = cache.get(repository, group, artifact);
+        match &cached {
+            Some(cached) if cached.is_fresh(cache.ttl) => return Ok(cached.versions()),
+            None if self.offline => anyhow::bail!("{}:{} is not cached", group, artifact),
+            _ => {}
+        }
+
+        let fetched = if self.offline {
+            Err(anyhow::anyhow!("offline"))
+        } else {
+            self.get_versions_from(repository, group, artifact).await
+        };
+        match (fetched, cached) {
+            (Ok(versions), _) => {
+                // the cache is only an optimization, so failing to write it is fine
+                let _ = cache.put(repository, group, artifact, &versions);
+                Ok(versions)
+            }
+            (Err(_), Some(cached)) => {
+                self.stale
+                    .lock()
+                    .unwrap()
+                    .insert((group.clone(), artifact.clone()));
+                Ok(cached.versions())
+            }
+            (Err(err), None) => Err(err),
+        }
+    }
+
+    /// Fetches the POM of the given artifact version from the first repository that
+    /// has a readable one.
+    async fn get_pom(
+        &self,
+        group: &Group,
+        artifact: &Artifact,
+        version: &Version,
+    ) -> Result<(Pom, String)> {
+        let mut last_error = None;
+        for repository in &self.repositories {
+            let artifact_url = format!("{}/{}", repository.artifact_url(group, artifact), version);
+            let url = format!("{}/{}-{}.pom", artifact_url, artifact.value, version);
+            match self.fetch_url(&url).await {
+                Ok(body) => match Pom::parse(&body) {
+                    Ok(pom) => return Ok((pom, format!("{}/", artifact_url))),
+                    Err(err) => last_error = Some(err.context(format!("Invalid POM at {}", url))),
+                },
+                Err(err) => last_error = Some(err),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No repositories configured")))
+    }
+
+    /// The source repository, homepage and release tag of the first candidate
+    /// artifact with a POM for the version. Whatever that POM leaves out is
+    /// looked up in its parent POMs.
+    pub async fn get_project_links(
+        &self,
+        group: &Group,
+        candidates: &[Artifact],
+        version: &Version,
+    ) -> Result<ProjectLinks> {
+        let mut last_error = None;
+        let mut found = None;
+        for candidate in candidates {
+            match self.get_pom(group, candidate, version).await {
+                Ok(pom) => {
+                    found = Some(pom);
+                    break;
+                }
+                Err(err) => last_error = Some(err),
+            }
+        }
+        let Some((pom, artifact_url)) = found else {
+            return Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No artifacts to look up")));
+        };
+
+        let mut links = ProjectLinks {
+            tag: pom
+                .scm
+                .as_ref()
+                .and_then(|scm| scm.tag.clone())
+                .filter(|tag| tag.trim() != "HEAD"),
+            artifact_url: Some(artifact_url),
+            ..ProjectLinks::default()
+        };
+        links.inherit(&pom);
+
+        let mut parent = pom.parent;
+        for _ in 0..MAX_PARENT_DEPTH {
+            if links.is_complete() {
+                break;
+            }
+            let Some((group, artifact, version)) = parent else {
+                break;
+            };
+            let Ok((pom, _)) = self.get_pom(&group, &artifact, &version).await else {
+                break;
+            };
+            links.inherit(&pom);
+            parent = pom.parent;
+        }
+        Ok(links)
+    }
 
-        #[derive(Deserialize, Debug)]
-        struct Project {
-            scm: Option<Scm>,
+    /// Where each artifact has moved to, according to the migrations table or
+    /// else a relocation in the POM of its newest version, along with the newest
+    /// version at the new coordinates. Artifacts that have not moved are left out.
+    pub async fn lookup_relocations(
+        &self,
+        lookups: Vec<(
+            Group,
+            Artifact,
+            DependencyKind,
+            CrossVersion,
+            Option<Version>,
+        )>,
+        target: &BuildTarget,
+    ) -> HashMap<(Group, Artifact), Relocation> {
+        let futures =
+            lookups
+                .into_iter()
+                .map(|(group, artifact, kind, cross, latest)| async move {
+                    let relocation = self
+                        .get_relocation(&group, &artifact, kind, cross, latest.as_ref(), target)
+                        .await?;
+                    Some(((group, artifact), relocation))
+                });
+
+        futures::future::join_all(futures)
+            .await
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    async fn get_relocation(
+        &self,
+        group: &Group,
+        artifact: &Artifact,
+        kind: DependencyKind,
+        cross: CrossVersion,
+        latest: Option<&Version>,
+        target: &BuildTarget,
+    ) -> Option<Relocation> {
+        let (new_group, new_artifact, version, message) =
+            match Migration::find(&self.migrations, group, artifact) {
+                Some((new_group, new_artifact)) => (new_group, new_artifact, None, None),
+                None if self.offline => return None,
+                None => {
+                    let candidates = artifact_candidates(artifact, kind, cross, target);
+                    self.get_pom_relocation(group, artifact, &candidates, latest?)
+                        .await?
+                }
+            };
+        if (&new_group, &new_artifact) == (group, artifact) {
+            return None;
         }
 
-        #[derive(Deserialize, Debug)]
-        struct Scm {
-            url: Option<String>,
+        let lookups = self
+            .lookup_multiple_versions(
+                vec![(new_group.clone(), new_artifact.clone(), kind, cross)],
+                target,
+            )
+            .await;
+        let versions = lookups
+            .into_values()
+            .next()
+            .and_then(|lookup| lookup.versions.ok())
+            .unwrap_or_default();
+        let newest = versions
+            .iter()
+            .filter(|version| !version.is_pre_release())
+            .max()
+            .or(versions.iter().max())
+            .cloned();
+
+        Some(Relocation {
+            group: new_group,
+            artifact: new_artifact,
+            version: newest.or(version)?,
+            message,
+        })
+    }
+
+    /// The relocation in the POM of the first candidate artifact that has one for
+    /// the version. The relocated artifact keeps the candidate's suffix, e.g.
+    /// `_2.13`, which is dropped again.
+    async fn get_pom_relocation(
+        &self,
+        group: &Group,
+        artifact: &Artifact,
+        candidates: &[Artifact],
+        version: &Version,
+    ) -> Option<(Group, Artifact, Option<Version>, Option<String>)> {
+        for candidate in candidates {
+            let Ok((pom, _)) = self.get_pom(group, candidate, version).await else {
+                continue;
+            };
+            let relocation = pom.relocation?;
+            let suffix = candidate
+                .value
+                .strip_prefix(&artifact.value)
+                .unwrap_or_default();
+            let new_artifact = match &relocation.artifact_id {
+                Some(relocated) => relocated.strip_suffix(suffix).unwrap_or(relocated),
+                None => &artifact.value,
+            };
+            return Some((
+                relocation
+                    .group_id
+                    .as_deref()
+                    .map_or_else(|| group.clone(), Group::new),
+                Artifact::new(new_artifact),
+                relocation.version.as_deref().map(Version::new),
+                relocation.message,
+            ));
         }
+        None
+    }
+}
+
+/// How many parent POMs to follow looking for a project's links.
+const MAX_PARENT_DEPTH: usize = 5;
 
-        let project: Project = from_str(&body).context("Failed to parse POM XML response")?;
+/// The repository answered 404: it doesn't have what was asked for, as opposed
+/// to failing to answer.
+#[derive(Debug)]
+struct NotFound;
 
-        Ok(project.scm.and_then(|scm| scm.url))
+impl std::fmt::Display for NotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Not found")
     }
 }
 
+impl std::error::Error for NotFound {}
+
+fn is_not_found(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<NotFound>().is_some()
+}
+
+enum FetchError {
+    /// Worth retrying, e.g. after a 429, a 5xx or a timeout.
+    Transient {
+        error: anyhow::Error,
+        retry_after: Option<Duration>,
+    },
+    Fatal(anyhow::Error),
+}
+
 #[async_trait]
 impl PackageSearch for MavenPackageSearch {
     async fn search_artifacts(
@@ -91,66 +585,148 @@ impl PackageSearch for MavenPackageSearch {
         group: &Group,
         artifact_prefix: &str,
     ) -> Result<Vec<Artifact>> {
-        let url = format!(
-            "https://repo1.maven.org/maven2/{}/",
-            group.value.replace('.', "/")
-        );
+        let mut artifacts: Vec<Artifact> = Vec::new();
+        let mut last_error = None;
+        let mut any_succeeded = false;
+        for repository in &self.repositories {
+            let url = format!("{}/{}/", repository.url, group.value.replace('.', "/"));
+
+            let body = match self.fetch_url(&url).await {
+                Ok(body) => body,
+                Err(err) => {
+                    last_error = Some(err);
+                    continue;
+                }
+            };
+            any_succeeded = true;
 
-        let body = self.fetch_url(&url).await?;
+            let document = Html::parse_document(&body);
+            let selector = Selector::parse("a").unwrap();
 
-        let document = Html::parse_document(&body);
-        let selector = Selector::parse("a").unwrap();
-
-        let artifacts = document
-            .select(&selector)
-            .filter_map(|element| {
-                let href = element.value().attr("href")?;
+            for element in document.select(&selector) {
+                let Some(href) = element.value().attr("href") else {
+                    continue;
+                };
                 if href.starts_with(artifact_prefix) && href.ends_with('/') {
-                    Some(Artifact::new(href.trim_end_matches('/')))
-                } else {
-                    None
+                    let artifact = Artifact::new(href.trim_end_matches('/'));
+                    if !artifacts.contains(&artifact) {
+                        artifacts.push(artifact);
+                    }
                 }
-            })
-            .collect();
+            }
+        }
 
-        Ok(artifacts)
+        match last_error {
+            Some(err) if !any_succeeded => Err(err),
+            _ => Ok(artifacts),
+        }
     }
 
+    /// Reads the local caches, then queries every repository in the chain, and
+    /// merges their versions in that order. Fails when a repository could not be
+    /// read and no other repository has the artifact, whatever the local caches
+    /// hold, as they may well lag behind; only offline do they stand in for the
+    /// repositories. When none fails and none has it, there are no versions.
     async fn get_versions(&self, group: &Group, artifact: &Artifact) -> Result<Vec<Version>> {
-        let url = format!(
-            "https://repo1.maven.org/maven2/{}/{}/",
-            group.value.replace('.', "/"),
-            artifact.value
-        );
+        let mut versions: Vec<Version> = Vec::new();
+        if let Some(local) = &self.local {
+            versions = local.get_versions(group, artifact).await?;
+        }
+        let mut any_succeeded = self.offline && !versions.is_empty();
+
+        let results = futures::future::join_all(
+            self.repositories
+                .iter()
+                .map(|repository| self.get_cached_versions_from(repository, group, artifact)),
+        )
+        .await;
+
+        let mut last_error = None;
+        for (repository, result) in self.repositories.iter().zip(results) {
+            match result {
+                Ok(found) => {
+                    any_succeeded |= !found.is_empty();
+                    for version in found {
+                        if !versions.contains(&version) {
+                            versions.push(version);
+                        }
+                    }
+                }
+                Err(err) => last_error = Some(err.context(repository.name.clone())),
+            }
+        }
 
-        let body = self.fetch_url(&url).await?;
+        match last_error {
+            Some(err) if !any_succeeded => Err(err),
+            _ => Ok(versions),
+        }
+    }
 
-        let document = Html::parse_document(&body);
-        let selector = Selector::parse("a").unwrap();
+    /// The `lastUpdated` of `maven-metadata.xml` for the version it last released,
+    /// and otherwise the `Last-Modified` date of the version's POM.
+    async fn get_release_date(
+        &self,
+        group: &Group,
+        artifact: &Artifact,
+        version: &Version,
+    ) -> Option<ReleaseDate> {
+        let key = (group.clone(), artifact.clone(), version.clone());
+        if let Some(date) = self.release_dates.lock().unwrap().get(&key) {
+            return Some(*date);
+        }
+        if self.offline {
+            return None;
+        }
 
-        let versions = document
-            .select(&selector)
-            .filter_map(|element| {
-                let href = element.value().attr("href")?;
-                if href.ends_with('/') && href != "../" {
-                    Some(Version::new(href.trim_end_matches('/')))
-                } else {
-                    None
-                }
-            })
-            .collect();
+        for repository in &self.repositories {
+            let url = format!(
+                "{}/{}/{}-{}.pom",
+                repository.artifact_url(group, artifact),
+                version,
+                artifact.value,
+                version
+            );
+            let Ok(response) = self.send(Method::HEAD, &url).await else {
+                continue;
+            };
+            let date = response
+                .headers()
+                .get(reqwest::header::LAST_MODIFIED)
+                .and_then(|value| value.to_str().ok())
+                .and_then(ReleaseDate::parse_http_date);
+            if let Some(date) = date {
+                self.release_dates.lock().unwrap().insert(key, date);
+                return Some(date);
+            }
+        }
+        None
+    }
 
-        Ok(versions)
+    fn is_stale(&self, group: &Group, artifact: &Artifact) -> bool {
+        self.stale
+            .lock()
+            .unwrap()
+            .contains(&(group.clone(), artifact.clone()))
     }
 }
 
 #[cfg(test)]
 pub(crate) mod integration_tests {
     use super::*;
+    use crate::package_search::local::{Layout, LocalRepository};
     use env_logger;
     use itertools::Itertools;
     use log::error;
-    use tokio;
+    use pretty_assertions::assert_eq;
+    use std::{
+        fs,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+    use tokio::{
+        self,
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
 
     #[tokio::test]
     async fn test_maven_package_search() -> Result<()> {
@@ -185,6 +761,126 @@ pub(crate) mod integration_tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_local_versions_do_not_hide_remote_errors() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::create_dir_all(dir.path().join("dev/zio/zio_3/2.0.0"))?;
+        fs::write(dir.path().join("dev/zio/zio_3/2.0.0/zio_3-2.0.0.pom"), "")?;
+        let local = LocalPackageSearch::new(vec![LocalRepository::new(
+            dir.path().to_path_buf(),
+            Layout::Maven,
+        )]);
+        let maven_search = MavenPackageSearch {
+            repositories: vec![Repository::new("unreachable", "http://127.0.0.1:1")],
+            retry_policy: RetryPolicy {
+                max_retries: 0,
+                ..RetryPolicy::default()
+            },
+            ..MavenPackageSearch::new()
+        }
+        .with_local(local);
+
+        let group = Group::new("dev.zio");
+        let artifact = Artifact::new("zio_3");
+        assert!(maven_search.get_versions(&group, &artifact).await.is_err());
+
+        let offline = MavenPackageSearch {
+            offline: true,
+            ..maven_search
+        };
+        assert_eq!(
+            offline.get_versions(&group, &artifact).await?,
+            vec![Version::new("2.0.0")]
+        );
+        Ok(())
+    }
+
+    /// Serves the body `respond` gives for each requested path, or a 404, standing
+    /// in for a repository. Counts the requests it receives.
+    async fn serve(respond: fn(&str) -> Option<&'static str>) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let mut request = vec![0; 4096];
+                let read = socket.read(&mut request).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&request[..read]).to_string();
+                let path = request.split_whitespace().nth(1).unwrap_or("");
+                let response = match respond(path) {
+                    Some(body) => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    ),
+                    None => {
+                        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                            .to_string()
+                    }
+                };
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        (format!("http://{}", address), requests)
+    }
+
+    #[tokio::test]
+    async fn test_get_pom_skips_unreadable_poms() -> Result<()> {
+        let (address, _) = serve(|path| {
+            Some(if path.starts_with("/broken/") {
+                "<html>Service unavailable"
+            } else {
+                "<project><url>https://zio.dev</url></project>"
+            })
+        })
+        .await;
+        let maven_search = MavenPackageSearch {
+            repositories: vec![
+                Repository::new("broken", &format!("{}/broken", address)),
+                Repository::new("good", &format!("{}/good", address)),
+            ],
+            ..MavenPackageSearch::new()
+        };
+
+        let (pom, artifact_url) = maven_search
+            .get_pom(
+                &Group::new("dev.zio"),
+                &Artifact::new("zio_3"),
+                &Version::new("2.0.0"),
+            )
+            .await?;
+        assert_eq!(pom.url.as_deref(), Some("https://zio.dev"));
+        assert!(artifact_url.contains("/good/"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_missing_artifacts_are_cached() -> Result<()> {
+        let (address, requests) = serve(|_| None).await;
+        let dir = tempfile::tempdir()?;
+        let maven_search = MavenPackageSearch {
+            repositories: vec![Repository::new("internal", &address)],
+            ..MavenPackageSearch::new()
+        }
+        .with_cache(VersionCache::new(
+            dir.path().to_path_buf(),
+            Duration::from_secs(60 * 60),
+        ));
+
+        let group = Group::new("dev.zio");
+        let artifact = Artifact::new("zio_3");
+        assert_eq!(maven_search.get_versions(&group, &artifact).await?, vec![]);
+        let after_lookup = requests.load(Ordering::SeqCst);
+        assert!(after_lookup > 0);
+
+        // within the TTL, a 404 is answered from the cache like any other lookup
+        assert_eq!(maven_search.get_versions(&group, &artifact).await?, vec![]);
+        assert_eq!(requests.load(Ordering::SeqCst), after_lookup);
+        Ok(())
+    }
+
     // get versions for https://repo1.maven.org/maven2/dev/zio/zio-json_2.13/
     #[tokio::test]
     async fn test_get_versions() -> Result<()> {
@@ -196,21 +892,25 @@ pub(crate) mod integration_tests {
         Ok(())
     }
 
-    // TODO: fix plugin search
+    // sbt plugins are looked up by their sbt-plugin publication, not as libraries
     #[tokio::test]
     async fn test_get_scala_native_packager() -> Result<()> {
         let group_id = Group::new("org.scalameta");
-        let artifact_id = Artifact::new("sbt-scalafmt_2.12_1.0");
-        let maven_search = MavenPackageSearch::new();
-
-        let found_artifact = maven_search
-            .search_artifacts(&group_id, &artifact_id.value)
-            .await?;
-        println!("Found artifacts: {:?}", found_artifact);
+        let target = BuildTarget {
+            sbt_version: Some(Version::new("1.10.0")),
+            ..BuildTarget::default()
+        };
+        let candidates = artifact_candidates(
+            &Artifact::new("sbt-scalafmt"),
+            DependencyKind::SbtPlugin,
+            CrossVersion::Binary,
+            &target,
+        );
+        assert_eq!(candidates, vec![Artifact::new("sbt-scalafmt_2.12_1.0")]);
 
-        // Get versions for the sbt-native-packager artifact
-        let versions = maven_search.get_versions(&group_id, &artifact_id).await?;
-        println!("Versions for 'sbt-native-packager': {:?}", versions);
+        let maven_search = MavenPackageSearch::new();
+        let versions = maven_search.get_versions(&group_id, &candidates[0]).await?;
+        assert!(versions.contains(&Version::new("2.5.2")));
 
         Ok(())
     }
@@ -222,13 +922,22 @@ pub(crate) mod integration_tests {
         let maven_search = MavenPackageSearch::new();
         let version = Version::new("0.7.0");
 
-        let repo = maven_search
-            .get_github_repo(&group_id, &artifact_id, &version)
-            .await
-            .unwrap();
+        let candidates = artifact_candidates(
+            &artifact_id,
+            DependencyKind::Library,
+            CrossVersion::Binary,
+            &BuildTarget::default(),
+        );
+        let links = maven_search
+            .get_project_links(&group_id, &candidates, &version)
+            .await?;
 
         // https://github.com/zio/zio-json/releases/tag/v0.7.0
-        let release_url = format!("{}/releases/tag/v{}", repo.unwrap(), version.to_string());
+        let release_url = format!(
+            "{}/releases/tag/v{}",
+            links.github_repository().unwrap(),
+            version
+        );
 
         // if webbrowser::open(&release_url).is_err() {
         //     println!("Failed to open the URL in the browser: {}", release_url);
@@ -0,0 +1,99 @@
+use anyhow::{Context, Result};
+use quick_xml::de::from_str;
+use serde::Deserialize;
+
+use crate::model::Version;
+
+/// The contents of an artifact's `maven-metadata.xml`, which every Maven-layout
+/// repository serves regardless of how (or whether) it renders directory listings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MavenMetadata {
+    pub versions: Vec<Version>,
+    pub latest: Option<Version>,
+    pub release: Option<Version>,
+    /// When the metadata was last updated, as `yyyyMMddHHmmss` in UTC.
+    pub last_updated: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Metadata {
+    versioning: Option<Versioning>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Versioning {
+    latest: Option<String>,
+    release: Option<String>,
+    versions: Option<Versions>,
+    last_updated: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Versions {
+    #[serde(default)]
+    version: Vec<String>,
+}
+
+impl MavenMetadata {
+    pub fn parse(xml: &str) -> Result<MavenMetadata> {
+        let metadata: Metadata = from_str(xml).context("Failed to parse maven-metadata.xml")?;
+        let versioning = metadata
+            .versioning
+            .context("maven-metadata.xml has no <versioning>")?;
+
+        let version = |v: String| Version::new(v.trim());
+        Ok(MavenMetadata {
+            versions: versioning
+                .versions
+                .map(|versions| versions.version.into_iter().map(version).collect())
+                .unwrap_or_default(),
+            latest: versioning.latest.map(version),
+            release: versioning.release.map(version),
+            last_updated: versioning.last_updated,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_maven_metadata() -> Result<()> {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<metadata>
+  <groupId>dev.zio</groupId>
+  <artifactId>zio_3</artifactId>
+  <versioning>
+    <latest>2.1.0-RC1</latest>
+    <release>2.0.21</release>
+    <versions>
+      <version>2.0.20</version>
+      <version>2.0.21</version>
+      <version>2.1.0-RC1</version>
+    </versions>
+    <lastUpdated>20240312093015</lastUpdated>
+  </versioning>
+</metadata>
+"#;
+
+        assert_eq!(
+            MavenMetadata::parse(xml)?,
+            MavenMetadata {
+                versions: vec![
+                    Version::new("2.0.20"),
+                    Version::new("2.0.21"),
+                    Version::new("2.1.0-RC1"),
+                ],
+                latest: Some(Version::new("2.1.0-RC1")),
+                release: Some(Version::new("2.0.21")),
+                last_updated: Some("20240312093015".to_string()),
+            }
+        );
+        assert!(MavenMetadata::parse("<html><body>Not found</body></html>").is_err());
+
+        Ok(())
+    }
+}
@@ -1,87 +1,334 @@
+pub mod cache;
+pub mod credentials;
+pub mod http;
+pub mod local;
 pub mod maven;
+pub mod metadata;
+pub mod pom;
+pub mod retry;
 
-use crate::model::{Artifact, Group, Version};
-use anyhow::Result;
+use crate::model::{
+    Artifact, BuildTarget, CrossVersion, DependencyKind, Group, Platform, ReleaseDate, Version,
+};
+use anyhow::{Context, Result};
 use async_trait::async_trait;
 use std::collections::HashMap;
 
+/// The outcome of looking up the versions of a single artifact.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionLookup {
+    pub versions: std::result::Result<Vec<Version>, String>,
+    /// Whether the versions came from an expired cache entry, e.g. when offline.
+    pub stale: bool,
+}
+
+/// When each version of each artifact was published, `None` where no repository knows.
+pub type ReleaseDates = HashMap<(Group, Artifact), HashMap<Version, Option<ReleaseDate>>>;
+
 #[async_trait]
 pub trait PackageSearch {
     async fn search_artifacts(&self, group: &Group, artifact_prefix: &str)
         -> Result<Vec<Artifact>>;
 
     async fn get_versions(&self, group: &Group, artifact: &Artifact) -> Result<Vec<Version>>;
+
+    /// When the version was published, if the repository says so.
+    async fn get_release_date(
+        &self,
+        _group: &Group,
+        _artifact: &Artifact,
+        _version: &Version,
+    ) -> Option<ReleaseDate> {
+        None
+    }
+
+    /// Whether the versions last returned for the artifact may be out of date.
+    fn is_stale(&self, _group: &Group, _artifact: &Artifact) -> bool {
+        false
+    }
 }
 
 #[async_trait]
 pub trait PackageSearchExt: PackageSearch {
+    /// The versions of the first suffixed artifact that has any. Only an artifact
+    /// that doesn't exist moves on to the next suffix: a failed lookup would
+    /// otherwise quietly offer the versions of a less preferred artifact.
     async fn get_firsts_with_suffix(
         &self,
         group: &Group,
         artifact: &Artifact,
         suffixes: Vec<&str>,
     ) -> Result<Vec<Version>> {
+        let found = self
+            .find_first_with_suffix(group, artifact, suffixes)
+            .await?;
+        Ok(found.map(|(_, versions)| versions).unwrap_or_default())
+    }
+
+    /// Like [`PackageSearchExt::get_firsts_with_suffix`], along with the suffixed
+    /// artifact the versions were found under.
+    async fn find_first_with_suffix(
+        &self,
+        group: &Group,
+        artifact: &Artifact,
+        suffixes: Vec<&str>,
+    ) -> Result<Option<(Artifact, Vec<Version>)>> {
         for suffix in suffixes {
             let artifact_with_suffix = Artifact::new(&format!("{}{}", artifact.value, suffix));
-            match self.get_versions(group, &artifact_with_suffix).await {
-                Ok(versions) if !versions.is_empty() => {
-                    return Ok(versions);
-                }
-                _ => continue,
+            let versions = self
+                .get_versions(group, &artifact_with_suffix)
+                .await
+                .with_context(|| format!("Failed to find versions for {}:{}", group, artifact))?;
+            if !versions.is_empty() {
+                return Ok(Some((artifact_with_suffix, versions)));
             }
         }
-        Ok(vec![])
+        Ok(None)
     }
 
-    async fn get_multiple_versions(
+    /// The versions published under every set of suffixes, where each set lists
+    /// alternatives in order of preference. Used to find the versions of a `%%%`
+    /// dependency that exist for every targeted platform.
+    async fn get_common_versions(
         &self,
-        group_artifact_pairs: Vec<(Group, Artifact)>,
-        maybe_scala_version: Option<Version>,
-    ) -> Result<HashMap<(Group, Artifact), Vec<Version>>> {
-        let suffixes = match maybe_scala_version {
-            Some(scala_version) if scala_version.major() == Some(3) => {
-                vec!["_3", "_2.13", "_2.12", "_2.12_1.0", ""]
-            }
-            Some(scala_version)
-                if scala_version.major() == Some(2) && scala_version.minor() == Some(13) =>
-            {
-                vec!["_2.13", "_2.12", "_2.12_1.0", ""]
-            }
-            Some(scala_version)
-                if scala_version.major() == Some(2) && scala_version.minor() == Some(12) =>
+        group: &Group,
+        artifact: &Artifact,
+        suffix_sets: Vec<Vec<String>>,
+    ) -> Result<Vec<Version>> {
+        let (versions, _) = self
+            .find_common_versions(group, artifact, suffix_sets)
+            .await?;
+        Ok(versions)
+    }
+
+    /// Like [`PackageSearchExt::get_common_versions`], along with the suffixed
+    /// artifacts the versions were found under.
+    async fn find_common_versions(
+        &self,
+        group: &Group,
+        artifact: &Artifact,
+        suffix_sets: Vec<Vec<String>>,
+    ) -> Result<(Vec<Version>, Vec<Artifact>)> {
+        let mut common: Option<Vec<Version>> = None;
+        let mut found_under = Vec::new();
+        for suffixes in suffix_sets {
+            let suffixes = suffixes.iter().map(String::as_str).collect();
+            let versions = match self
+                .find_first_with_suffix(group, artifact, suffixes)
+                .await?
             {
-                vec!["_2.12", "_2.12_1.0", ""]
-            }
-            _ => vec!["_2.13", "_3", "_2.12", "_2.12_1.0", ""],
-        };
+                Some((suffixed, versions)) => {
+                    found_under.push(suffixed);
+                    versions
+                }
+                None => Vec::new(),
+            };
+            common = Some(match common {
+                None => versions,
+                Some(common) => common
+                    .into_iter()
+                    .filter(|version| versions.contains(version))
+                    .collect(),
+            });
+        }
+        Ok((common.unwrap_or_default(), found_under))
+    }
 
-        let futures = group_artifact_pairs.into_iter().map(|(group, artifact)| {
+    /// The versions of each dependency, as published for the build target. A failed
+    /// lookup keeps its error, rather than passing for an empty version list. A lookup is stale when
+    /// an artifact its versions were found under came from an expired cache entry.
+    async fn lookup_multiple_versions(
+        &self,
+        lookups: Vec<(Group, Artifact, DependencyKind, CrossVersion)>,
+        target: &BuildTarget,
+    ) -> HashMap<(Group, Artifact), VersionLookup> {
+        let futures = lookups.into_iter().map(|(group, artifact, kind, cross)| {
             let group_clone = group.clone();
             let artifact_clone = artifact.clone();
-            let suffixes_clone = suffixes.clone();
+            let suffix_sets = suffix_sets(kind, cross, target);
             async move {
-                let versions = self
-                    .get_firsts_with_suffix(&group, &artifact, suffixes_clone)
+                let found = self
+                    .find_common_versions(&group, &artifact, suffix_sets)
                     .await;
-                ((group_clone, artifact_clone), versions)
+                let stale = found.as_ref().is_ok_and(|(_, found_under)| {
+                    found_under
+                        .iter()
+                        .any(|suffixed| self.is_stale(&group, suffixed))
+                });
+                let lookup = VersionLookup {
+                    versions: found
+                        .map(|(versions, _)| versions)
+                        .map_err(|err| format!("{:#}", err)),
+                    stale,
+                };
+                ((group_clone, artifact_clone), lookup)
             }
         });
 
-        let results = futures::future::join_all(futures).await;
+        futures::future::join_all(futures)
+            .await
+            .into_iter()
+            .collect()
+    }
+
+    /// The release dates of the given versions of each artifact, `None` where no
+    /// repository knows. Each version is dated by the first suffixed artifact that has it.
+    async fn lookup_release_dates(
+        &self,
+        lookups: Vec<(Group, Artifact, DependencyKind, CrossVersion, Vec<Version>)>,
+        target: &BuildTarget,
+    ) -> ReleaseDates {
+        let futures = lookups
+            .into_iter()
+            .flat_map(|(group, artifact, kind, cross, versions)| {
+                let candidates = artifact_candidates(&artifact, kind, cross, target);
+                versions.into_iter().map(move |version| {
+                    let (group, artifact, candidates) =
+                        (group.clone(), artifact.clone(), candidates.clone());
+                    async move {
+                        let mut date = None;
+                        for candidate in &candidates {
+                            date = self.get_release_date(&group, candidate, &version).await;
+                            if date.is_some() {
+                                break;
+                            }
+                        }
+                        ((group, artifact), version, date)
+                    }
+                })
+            });
 
-        let mut versions_map = HashMap::new();
-        for ((group, artifact), versions_result) in results {
-            if let Ok(versions) = versions_result {
-                versions_map.insert((group, artifact), versions);
-            }
+        let mut dates = ReleaseDates::new();
+        for (key, version, date) in futures::future::join_all(futures).await {
+            dates.entry(key).or_default().insert(version, date);
         }
-
-        Ok(versions_map)
+        dates
     }
 }
 
 impl<T: PackageSearch + Sync> PackageSearchExt for T {}
 
+/// Every suffixed artifact a dependency may be published as, in order of preference.
+pub fn artifact_candidates(
+    artifact: &Artifact,
+    kind: DependencyKind,
+    cross: CrossVersion,
+    target: &BuildTarget,
+) -> Vec<Artifact> {
+    suffix_sets(kind, cross, target)
+        .into_iter()
+        .flatten()
+        .map(|suffix| Artifact::new(&format!("{}{}", artifact.value, suffix)))
+        .collect()
+}
+
+/// The suffix sets to look an artifact up by, as used by
+/// [`PackageSearchExt::get_common_versions`].
+fn suffix_sets(
+    kind: DependencyKind,
+    cross: CrossVersion,
+    target: &BuildTarget,
+) -> Vec<Vec<String>> {
+    match (kind, cross) {
+        (DependencyKind::SbtPlugin, _) => vec![sbt_plugin_suffixes(target)],
+        (DependencyKind::Toolchain, _) | (_, CrossVersion::Disabled) => vec![vec![String::new()]],
+        (_, cross) if cross.is_platform() => platform_suffixes(cross, target),
+        (_, cross) => scala_suffix_sets(cross, target),
+    }
+}
+
+/// The Scala version suffixes of a `%%` dependency, one set per Scala version
+/// the build is compiled with, so that only versions published for all of them
+/// are offered.
+fn scala_suffix_sets(cross: CrossVersion, target: &BuildTarget) -> Vec<Vec<String>> {
+    let mut sets: Vec<Vec<String>> = Vec::new();
+    for version in &target.scala_versions {
+        let suffixes = scala_suffixes(cross, Some(version));
+        if !sets.contains(&suffixes) {
+            sets.push(suffixes);
+        }
+    }
+    if sets.is_empty() {
+        sets.push(scala_suffixes(cross, None));
+    }
+    sets
+}
+
+/// The Scala version suffixes for a Scala version, in order of preference.
+/// Scala 3 can use Scala 2.13 artifacts, but not the other way around.
+fn scala_suffixes(cross: CrossVersion, scala_version: Option<&Version>) -> Vec<String> {
+    let any = || vec!["_2.13".to_string(), "_3".to_string(), "_2.12".to_string()];
+    let Some(version) = scala_version else {
+        return match cross {
+            // without the Scala version there is no telling what it is suffixed with
+            CrossVersion::Full => vec![],
+            _ => any(),
+        };
+    };
+    match (cross, version.major(), version.minor()) {
+        (CrossVersion::Full, _, _) => vec![format!("_{}", version)],
+        (CrossVersion::For3Use2_13 { .. }, Some(3), _) => vec!["_2.13".to_string()],
+        (CrossVersion::For2_13Use3 { .. }, Some(2), Some(13)) => vec!["_3".to_string()],
+        (_, Some(3), _) => vec!["_3".to_string(), "_2.13".to_string()],
+        (_, Some(2), Some(minor)) => vec![format!("_2.{}", minor)],
+        _ => any(),
+    }
+}
+
+/// The suffixes of a `%%%` dependency, one set per targeted platform and Scala
+/// version, e.g. `_sjs1_3`. On the JVM alone this is the same as `%%`.
+fn platform_suffixes(cross: CrossVersion, target: &BuildTarget) -> Vec<Vec<String>> {
+    let scala_sets = scala_suffix_sets(cross, target);
+    let with_prefixes = |prefixes: &[String], scala_suffixes: &[String]| {
+        prefixes
+            .iter()
+            .flat_map(|prefix| {
+                scala_suffixes
+                    .iter()
+                    .map(move |s| format!("_{}{}", prefix, s))
+            })
+            .collect()
+    };
+
+    let platforms = if target.platforms.is_empty() {
+        vec![Platform::Jvm]
+    } else {
+        target.platforms.clone()
+    };
+    platforms
+        .iter()
+        .flat_map(|platform| {
+            scala_sets.iter().map(move |scala_suffixes| match platform {
+                Platform::Jvm => scala_suffixes.clone(),
+                Platform::Js => with_prefixes(&["sjs1".to_string()], scala_suffixes),
+                Platform::Native => with_prefixes(&scala_native_binaries(target), scala_suffixes),
+            })
+        })
+        .collect()
+}
+
+fn scala_native_binaries(target: &BuildTarget) -> Vec<String> {
+    match target
+        .scala_native_version
+        .as_ref()
+        .and_then(|v| Some((v.major()?, v.minor()?)))
+    {
+        Some((major, minor)) => vec![format!("native{}.{}", major, minor)],
+        None => vec!["native0.5".to_string(), "native0.4".to_string()],
+    }
+}
+
+/// sbt plugins are published Maven-style with the sbt and Scala binary versions
+/// as a suffix: `_2.12_1.0` for sbt 1 and `_sbt2_3` for sbt 2.
+fn sbt_plugin_suffixes(target: &BuildTarget) -> Vec<String> {
+    let suffixes = match target.sbt_version.as_ref().and_then(|v| v.major()) {
+        Some(1) => vec!["_2.12_1.0"],
+        Some(2) => vec!["_sbt2_3"],
+        _ => vec!["_2.12_1.0", "_sbt2_3"],
+    };
+    suffixes.into_iter().map(String::from).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::Path;
@@ -94,6 +341,200 @@ mod tests {
     use itertools::Itertools;
     use tokio;
 
+    #[test]
+    fn test_platform_suffixes() {
+        let target = BuildTarget {
+            scala_versions: vec![Version::new("3.3.1")],
+            platforms: vec![Platform::Jvm, Platform::Js, Platform::Native],
+            scala_native_version: Some(Version::new("0.4.17")),
+            ..Default::default()
+        };
+        assert_eq!(
+            platform_suffixes(CrossVersion::Platform, &target),
+            vec![
+                vec!["_3", "_2.13"],
+                vec!["_sjs1_3", "_sjs1_2.13"],
+                vec!["_native0.4_3", "_native0.4_2.13"],
+            ]
+        );
+
+        let jvm_only = BuildTarget::new(vec![Version::new("2.13.12")]);
+        assert_eq!(
+            platform_suffixes(CrossVersion::Platform, &jvm_only),
+            vec![vec!["_2.13"]]
+        );
+
+        let cross_built = BuildTarget {
+            scala_versions: vec![
+                Version::new("2.12.19"),
+                Version::new("2.13.14"),
+                Version::new("3.3.3"),
+            ],
+            platforms: vec![Platform::Jvm, Platform::Js],
+            ..Default::default()
+        };
+        assert_eq!(
+            platform_suffixes(CrossVersion::Platform, &cross_built),
+            vec![
+                vec!["_2.12"],
+                vec!["_2.13"],
+                vec!["_3", "_2.13"],
+                vec!["_sjs1_2.12"],
+                vec!["_sjs1_2.13"],
+                vec!["_sjs1_3", "_sjs1_2.13"],
+            ]
+        );
+        assert_eq!(
+            suffix_sets(DependencyKind::Library, CrossVersion::Binary, &cross_built),
+            vec![vec!["_2.12"], vec!["_2.13"], vec!["_3", "_2.13"]]
+        );
+        assert_eq!(
+            suffix_sets(
+                DependencyKind::Library,
+                CrossVersion::For3Use2_13 { platform: false },
+                &cross_built
+            ),
+            vec![vec!["_2.12"], vec!["_2.13"]]
+        );
+        assert_eq!(
+            suffix_sets(
+                DependencyKind::Library,
+                CrossVersion::For3Use2_13 { platform: true },
+                &BuildTarget {
+                    scala_versions: vec![Version::new("3.3.3")],
+                    platforms: vec![Platform::Js],
+                    ..Default::default()
+                }
+            ),
+            vec![vec!["_sjs1_2.13"]]
+        );
+        assert_eq!(
+            suffix_sets(DependencyKind::Library, CrossVersion::Full, &cross_built),
+            vec![vec!["_2.12.19"], vec!["_2.13.14"], vec!["_3.3.3"]]
+        );
+        assert_eq!(
+            artifact_candidates(
+                &Artifact::new("kind-projector"),
+                DependencyKind::CompilerPlugin,
+                CrossVersion::Full,
+                &BuildTarget::new(vec![Version::new("2.13.14")])
+            ),
+            vec![Artifact::new("kind-projector_2.13.14")]
+        );
+    }
+
+    /// Answers every artifact from a fixed table, failing for those mapped to `None`.
+    /// The artifacts in the second field were answered from expired cache entries.
+    struct FakePackageSearch(
+        HashMap<&'static str, Option<Vec<&'static str>>>,
+        Vec<&'static str>,
+    );
+
+    #[async_trait]
+    impl PackageSearch for FakePackageSearch {
+        async fn search_artifacts(&self, _: &Group, _: &str) -> Result<Vec<Artifact>> {
+            Ok(vec![])
+        }
+
+        async fn get_versions(&self, _: &Group, artifact: &Artifact) -> Result<Vec<Version>> {
+            match self.0.get(artifact.value.as_str()) {
+                Some(Some(versions)) => Ok(versions.iter().map(|v| Version::new(v)).collect()),
+                Some(None) => anyhow::bail!("Request failed with status: 503"),
+                None => Ok(vec![]),
+            }
+        }
+
+        fn is_stale(&self, _: &Group, artifact: &Artifact) -> bool {
+            self.1.contains(&artifact.value.as_str())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_firsts_with_suffix() -> Result<()> {
+        let search = FakePackageSearch(
+            HashMap::from([("zio_3", None), ("zio_2.13", Some(vec!["2.0.0"]))]),
+            vec![],
+        );
+        let (group, artifact) = (Group::new("dev.zio"), Artifact::new("zio"));
+
+        // a failure of the preferred artifact is not papered over by the next one
+        let failed = search
+            .get_firsts_with_suffix(&group, &artifact, vec!["_3", "_2.13"])
+            .await;
+        assert!(failed.is_err());
+
+        // whereas one that doesn't exist is
+        let versions = search
+            .get_firsts_with_suffix(&group, &artifact, vec!["_2.12", "_2.13"])
+            .await?;
+        assert_eq!(versions, vec![Version::new("2.0.0")]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_only_the_artifact_looked_up_makes_a_lookup_stale() {
+        let search = FakePackageSearch(
+            HashMap::from([
+                ("zio_3", Some(vec!["2.1.0"])),
+                ("zio_2.13", Some(vec!["2.0.0"])),
+                ("cats-core_2.13", Some(vec!["2.10.0"])),
+            ]),
+            vec!["zio_2.13", "cats-core_2.13"],
+        );
+        let group = Group::new("dev.zio");
+        let lookups = search
+            .lookup_multiple_versions(
+                vec![
+                    (
+                        group.clone(),
+                        Artifact::new("zio"),
+                        DependencyKind::Library,
+                        CrossVersion::Binary,
+                    ),
+                    (
+                        group.clone(),
+                        Artifact::new("cats-core"),
+                        DependencyKind::Library,
+                        CrossVersion::Binary,
+                    ),
+                ],
+                &BuildTarget::new(vec![Version::new("3.3.1")]),
+            )
+            .await;
+
+        // the fresh Scala 3 artifact is used, not the stale 2.13 one
+        assert!(!lookups[&(group.clone(), Artifact::new("zio"))].stale);
+        assert!(lookups[&(group, Artifact::new("cats-core"))].stale);
+    }
+
+    fn library_lookups(
+        pairs: Vec<(Group, Artifact)>,
+    ) -> Vec<(Group, Artifact, DependencyKind, CrossVersion)> {
+        pairs
+            .into_iter()
+            .map(|(group, artifact)| {
+                (
+                    group,
+                    artifact,
+                    DependencyKind::Library,
+                    CrossVersion::Binary,
+                )
+            })
+            .collect()
+    }
+
+    fn warn_about_failed_lookups(lookups: &HashMap<(Group, Artifact), VersionLookup>) {
+        for ((group, artifact), lookup) in lookups {
+            match &lookup.versions {
+                Ok(versions) if versions.is_empty() => {
+                    eprintln!("Warning: Version list for {}:{} is empty", group, artifact)
+                }
+                Ok(_) => {}
+                Err(err) => eprintln!("Warning: could not look up {}:{}: {}", group, artifact, err),
+            }
+        }
+    }
+
     #[tokio::test]
     async fn test_get_versions_for_scala_js() -> Result<()> {
         let maven_search = MavenPackageSearch::new();
@@ -107,14 +548,19 @@ mod tests {
             (Group::new("io.spray"), Artifact::new("sbt-revolver")),
             (Group::new("dev.zio"), Artifact::new("zio")),
         ];
-        let versions = maven_search
-            .get_multiple_versions(group_artifact_pairs, Some(Version::new("3.0.0")))
-            .await?;
+        let lookups = maven_search
+            .lookup_multiple_versions(
+                library_lookups(group_artifact_pairs),
+                &BuildTarget::new(vec![Version::new("3.0.0")]),
+            )
+            .await;
 
         println!("VERSIONS");
-        for ((group, artifact), versions) in versions.iter() {
-            let highest_ever = versions.iter().join(", ");
-            println!("{}:{}::{}", group, artifact, highest_ever);
+        for ((group, artifact), lookup) in lookups.iter() {
+            match &lookup.versions {
+                Ok(versions) => println!("{}:{}::{}", group, artifact, versions.iter().join(", ")),
+                Err(err) => eprintln!("Warning: could not look up {}:{}: {}", group, artifact, err),
+            }
         }
 
         // assert!(!versions.is_empty(), "Versions list should not be empty");
@@ -124,7 +570,7 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_get_multiple_versions_for_dev_zio_libraries() -> Result<()> {
+    async fn test_lookup_multiple_versions_for_dev_zio_libraries() -> Result<()> {
         let maven_search = MavenPackageSearch::new();
 
         let group_artifact_pairs = vec![
@@ -133,21 +579,16 @@ mod tests {
             (Group::new("dev.zio"), Artifact::new("zio-schema")),
         ];
 
-        let versions_map = maven_search
-            .get_multiple_versions(group_artifact_pairs, None)
-            .await?;
+        let lookups = maven_search
+            .lookup_multiple_versions(
+                library_lookups(group_artifact_pairs),
+                &BuildTarget::default(),
+            )
+            .await;
 
-        assert_eq!(versions_map.len(), 3);
-        for ((group, artifact), version_list) in versions_map.iter() {
-            if version_list.is_empty() {
-                eprintln!(
-                    "Warning: Version list for {:?}/{:?} is empty",
-                    group, artifact
-                );
-            }
-        }
-
-        println!("Versions retrieved: {:?}", versions_map);
+        assert_eq!(lookups.len(), 3);
+        warn_about_failed_lookups(&lookups);
+        println!("Versions retrieved: {:?}", lookups);
 
         Ok(())
     }
@@ -160,25 +601,25 @@ mod tests {
 
         let all_groups_and_artifacts = dependencies
             .iter()
-            .map(|(dep, _)| (dep.0.clone(), Artifact::new(&format!("{}_3", dep.1.value))))
+            .map(|(key, _)| {
+                (
+                    key.group.clone(),
+                    Artifact::new(&format!("{}_3", key.artifact.value)),
+                )
+            })
             .collect::<Vec<_>>();
 
         let maven_search = MavenPackageSearch::new();
-        let versions_map = maven_search
-            .get_multiple_versions(all_groups_and_artifacts, None)
-            .await?;
-
-        assert!(!versions_map.is_empty(), "Versions map should not be empty");
-        for ((group, artifact), version_list) in versions_map.iter() {
-            if version_list.is_empty() {
-                eprintln!(
-                    "Warning: Version list for {:?}/{:?} is empty",
-                    group, artifact
-                );
-            }
-        }
+        let lookups = maven_search
+            .lookup_multiple_versions(
+                library_lookups(all_groups_and_artifacts),
+                &BuildTarget::default(),
+            )
+            .await;
 
-        println!("Versions retrieved: {:?}", versions_map);
+        assert!(!lookups.is_empty(), "Versions map should not be empty");
+        warn_about_failed_lookups(&lookups);
+        println!("Versions retrieved: {:?}", lookups);
 
         Ok(())
     }
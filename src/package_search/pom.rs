@@ -0,0 +1,303 @@
+use anyhow::{Context, Result};
+use quick_xml::de::from_str;
+use serde::Deserialize;
+
+use crate::model::{Artifact, Group, Version};
+
+/// The parts of a POM that say where a project lives.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Pom {
+    pub parent: Option<(Group, Artifact, Version)>,
+    /// The project's homepage, from `<url>`.
+    pub url: Option<String>,
+    pub scm: Option<Scm>,
+    /// Where the artifact has moved to, from `<distributionManagement><relocation>`.
+    pub relocation: Option<PomRelocation>,
+}
+
+/// The new coordinates of a relocated artifact. Whatever is left out stays the same.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PomRelocation {
+    pub group_id: Option<String>,
+    pub artifact_id: Option<String>,
+    pub version: Option<String>,
+    pub message: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Scm {
+    pub url: Option<String>,
+    pub connection: Option<String>,
+    pub developer_connection: Option<String>,
+    pub tag: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Project {
+    parent: Option<Parent>,
+    url: Option<String>,
+    scm: Option<Scm>,
+    distribution_management: Option<DistributionManagement>,
+}
+
+#[derive(Deserialize, Debug)]
+struct DistributionManagement {
+    relocation: Option<PomRelocation>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Parent {
+    group_id: String,
+    artifact_id: String,
+    version: String,
+}
+
+impl Pom {
+    pub fn parse(xml: &str) -> Result<Pom> {
+        let project: Project = from_str(xml).context("Failed to parse POM XML response")?;
+        Ok(Pom {
+            parent: project.parent.map(|parent| {
+                (
+                    Group::new(parent.group_id.trim()),
+                    Artifact::new(parent.artifact_id.trim()),
+                    Version::new(parent.version.trim()),
+                )
+            }),
+            url: project.url.map(|url| url.trim().to_string()),
+            scm: project.scm,
+            relocation: project
+                .distribution_management
+                .and_then(|management| management.relocation)
+                .map(|relocation| PomRelocation {
+                    group_id: trimmed(relocation.group_id),
+                    artifact_id: trimmed(relocation.artifact_id),
+                    version: trimmed(relocation.version),
+                    message: trimmed(relocation.message),
+                }),
+        })
+    }
+}
+
+fn trimmed(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+impl Scm {
+    /// A browsable URL of the source repository: `url`, else `connection`, else
+    /// `developerConnection`.
+    pub fn source_url(&self) -> Option<String> {
+        [&self.url, &self.connection, &self.developer_connection]
+            .into_iter()
+            .flatten()
+            .find_map(|url| normalize_scm_url(url))
+    }
+}
+
+/// Turns the many ways a POM spells a repository into a browsable URL, e.g.
+/// `scm:git:git@github.com:zio/zio.git` into `https://github.com/zio/zio`.
+pub fn normalize_scm_url(url: &str) -> Option<String> {
+    let mut url = url.trim();
+    // `scm:git:https://...` or `scm:git|https://...`
+    if let Some(rest) = url.strip_prefix("scm:") {
+        url = rest.split_once([':', '|']).map_or(rest, |(_, rest)| rest);
+    }
+
+    let url = if let Some(rest) = url.strip_prefix("git@") {
+        // git@github.com:zio/zio.git
+        format!("https://{}", rest.replacen(':', "/", 1))
+    } else if let Some((scheme, rest)) = url.split_once("://") {
+        // The user of ssh://git@github.com/zio/zio
+        let rest = match rest.split_once('@') {
+            Some((user, host)) if !user.contains('/') => host,
+            _ => rest,
+        };
+        let scheme = match scheme {
+            "git" | "ssh" | "git+ssh" | "http" => "https",
+            scheme => scheme,
+        };
+        format!("{}://{}", scheme, rest)
+    } else if url.starts_with("github.com/") {
+        format!("https://{}", url)
+    } else {
+        return None;
+    };
+
+    let url = url.trim_end_matches('/');
+    let url = url.strip_suffix(".git").unwrap_or(url);
+    Some(url.to_string()).filter(|url| url.contains('.'))
+}
+
+/// Where a project lives, gathered from its POM and, for whatever that leaves out,
+/// its parent POMs.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ProjectLinks {
+    /// The source repository.
+    pub source: Option<String>,
+    pub homepage: Option<String>,
+    /// The release's tag, if the POM names one.
+    pub tag: Option<String>,
+    /// The directory the POM was found in.
+    pub artifact_url: Option<String>,
+}
+
+impl ProjectLinks {
+    /// Fills in whatever is still missing from the given POM.
+    pub fn inherit(&mut self, pom: &Pom) {
+        if self.source.is_none() {
+            self.source = pom.scm.as_ref().and_then(Scm::source_url);
+        }
+        if self.homepage.is_none() {
+            self.homepage = pom.url.clone().filter(|url| url.contains("://"));
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.source.is_some() && self.homepage.is_some()
+    }
+
+    /// The GitHub repository, e.g. `https://github.com/zio/zio`, from the source
+    /// repository or else the homepage.
+    pub fn github_repository(&self) -> Option<String> {
+        [&self.source, &self.homepage]
+            .into_iter()
+            .flatten()
+            .find_map(|url| {
+                let (_, path) = url.split_once("github.com/")?;
+                let mut segments = path.split('/').filter(|segment| !segment.is_empty());
+                let (owner, repo) = (segments.next()?, segments.next()?);
+                Some(format!("https://github.com/{}/{}", owner, repo))
+            })
+    }
+
+    /// The page that best describes the release: the source repository, the
+    /// homepage, or as a last resort the artifact's directory in the repository.
+    pub fn best_url(&self) -> Option<&String> {
+        self.source
+            .as_ref()
+            .or(self.homepage.as_ref())
+            .or(self.artifact_url.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_normalize_scm_url() {
+        for (url, expected) in [
+            ("https://github.com/zio/zio", "https://github.com/zio/zio"),
+            (
+                "scm:git:git@github.com:zio/zio.git",
+                "https://github.com/zio/zio",
+            ),
+            (
+                "scm:git:https://github.com/zio/zio.git",
+                "https://github.com/zio/zio",
+            ),
+            (
+                "scm:git:ssh://git@github.com/zio/zio.git",
+                "https://github.com/zio/zio",
+            ),
+            (
+                "git://github.com/zio/zio.git/",
+                "https://github.com/zio/zio",
+            ),
+            ("github.com/zio/zio", "https://github.com/zio/zio"),
+            (
+                "scm:git:https://gitlab.com/group/project.git",
+                "https://gitlab.com/group/project",
+            ),
+        ] {
+            assert_eq!(
+                normalize_scm_url(url),
+                Some(expected.to_string()),
+                "{}",
+                url
+            );
+        }
+        assert_eq!(normalize_scm_url("scm:git:"), None);
+    }
+
+    #[test]
+    fn test_project_links_from_poms() -> Result<()> {
+        let pom = Pom::parse(
+            r#"<project>
+                <parent>
+                    <groupId>com.example</groupId>
+                    <artifactId>example-parent</artifactId>
+                    <version>3</version>
+                </parent>
+                <artifactId>example-core</artifactId>
+                <scm>
+                    <connection>scm:git:git@github.com:example/example.git</connection>
+                    <tag>example-1.2.3</tag>
+                </scm>
+            </project>"#,
+        )?;
+        assert_eq!(
+            pom.parent,
+            Some((
+                Group::new("com.example"),
+                Artifact::new("example-parent"),
+                Version::new("3")
+            ))
+        );
+
+        let parent = Pom::parse(
+            r#"<project>
+                <url>https://example.com</url>
+                <scm><url>https://github.com/example/parent</url></scm>
+            </project>"#,
+        )?;
+
+        let mut links = ProjectLinks::default();
+        links.inherit(&pom);
+        assert!(!links.is_complete());
+        links.inherit(&parent);
+        assert_eq!(
+            links.source,
+            Some("https://github.com/example/example".to_string())
+        );
+        assert_eq!(links.homepage, Some("https://example.com".to_string()));
+        assert_eq!(
+            links.github_repository(),
+            Some("https://github.com/example/example".to_string())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_relocation() -> Result<()> {
+        let pom = Pom::parse(
+            r#"<project>
+                <groupId>org.example</groupId>
+                <artifactId>example_2.13</artifactId>
+                <distributionManagement>
+                    <relocation>
+                        <groupId> com.example </groupId>
+                        <message>Moved to com.example</message>
+                    </relocation>
+                </distributionManagement>
+            </project>"#,
+        )?;
+        assert_eq!(
+            pom.relocation,
+            Some(PomRelocation {
+                group_id: Some("com.example".to_string()),
+                artifact_id: None,
+                version: None,
+                message: Some("Moved to com.example".to_string()),
+            })
+        );
+        assert_eq!(Pom::parse("<project></project>")?.relocation, None);
+        Ok(())
+    }
+}
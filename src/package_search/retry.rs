@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use reqwest::{header::HeaderMap, StatusCode};
+
+/// Never wait longer than this between attempts, whatever the server asks for.
+const MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// How often, and how patiently, to retry requests that fail transiently.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Rate limiting and server errors are worth retrying; anything else, e.g. a 404, is not.
+    pub fn is_retryable(status: StatusCode) -> bool {
+        status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+    }
+
+    /// The delay before the given retry (starting at 0): the server's `Retry-After`
+    /// if it sent one, and exponential backoff with jitter otherwise.
+    pub fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        let delay = retry_after.unwrap_or_else(|| {
+            let backoff = self.base_delay.saturating_mul(2u32.saturating_pow(attempt));
+            let jitter = backoff.mul_f64(rand::random::<f64>() * 0.25);
+            backoff + jitter
+        });
+        delay.min(MAX_DELAY)
+    }
+}
+
+/// Reads a `Retry-After` header given in seconds. HTTP dates are ignored, in
+/// favor of the usual backoff.
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?;
+    value.trim().parse().ok().map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use reqwest::header::{HeaderValue, RETRY_AFTER};
+
+    #[test]
+    fn test_retry_delay() {
+        let policy = RetryPolicy::default();
+
+        assert!(RetryPolicy::is_retryable(StatusCode::TOO_MANY_REQUESTS));
+        assert!(RetryPolicy::is_retryable(StatusCode::BAD_GATEWAY));
+        assert!(!RetryPolicy::is_retryable(StatusCode::NOT_FOUND));
+
+        let second = policy.delay(1, None);
+        assert!(second >= Duration::from_secs(1) && second <= Duration::from_millis(1250));
+        assert_eq!(policy.delay(20, None), MAX_DELAY);
+        assert_eq!(
+            policy.delay(0, Some(Duration::from_secs(7))),
+            Duration::from_secs(7)
+        );
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("120"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(120)));
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), None);
+    }
+}
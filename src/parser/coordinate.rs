@@ -0,0 +1,104 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use super::Span;
+use crate::model::{Artifact, CrossVersion, Group};
+
+static COORDINATE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^([^:\s]+)(:{1,3})([^:\s]+)(:{1,2})([^:;,\s]+)").unwrap());
+static CLASSIFIER_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[,;]classifier=([^,;\s]+)").unwrap());
+
+/// A dependency in the colon-separated notation used by Mill and scala-cli,
+/// e.g. `org.typelevel::cats-core:2.10.0`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Coordinate {
+    pub group: Group,
+    pub artifact: Artifact,
+    /// `group:artifact` is a Java artifact, `group::artifact` is Scala cross-versioned,
+    /// `group:::artifact` is cross-versioned with the full Scala version and
+    /// `group::artifact::version` is platform cross-versioned.
+    pub cross: CrossVersion,
+    pub version: String,
+    /// From an attribute after the version, e.g. `,classifier=tests`.
+    pub classifier: Option<String>,
+    /// The positions of the group, artifact and version, relative to the parsed text.
+    pub group_span: Span,
+    pub artifact_span: Span,
+    pub version_span: Span,
+}
+
+impl Coordinate {
+    /// Parses `group:artifact:version`, allowing `::` and `:::` separators.
+    /// Of the attributes after the version, only the classifier is kept.
+    pub fn parse(text: &str) -> Option<Coordinate> {
+        let captures = COORDINATE_RE.captures(text)?;
+        let (group, artifact, version) = (captures.get(1)?, captures.get(3)?, captures.get(5)?);
+        let cross = match (&captures[2], &captures[4]) {
+            (_, "::") => CrossVersion::Platform,
+            (":", _) => CrossVersion::Disabled,
+            (":::", _) => CrossVersion::Full,
+            _ => CrossVersion::Binary,
+        };
+        let classifier = CLASSIFIER_RE
+            .captures(&text[version.end()..])
+            .map(|captures| captures[1].to_string());
+
+        Some(Coordinate {
+            group: Group::new(group.as_str()),
+            artifact: Artifact::new(artifact.as_str()),
+            cross,
+            version: version.as_str().to_string(),
+            classifier,
+            group_span: Span::new(group.start(), group.end()),
+            artifact_span: Span::new(artifact.start(), artifact.end()),
+            version_span: Span::new(version.start(), version.end()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_coordinate() {
+        assert_eq!(
+            Coordinate::parse("com.lihaoyi::scalatags:0.12.0"),
+            Some(Coordinate {
+                group: Group::new("com.lihaoyi"),
+                artifact: Artifact::new("scalatags"),
+                cross: CrossVersion::Binary,
+                version: "0.12.0".to_string(),
+                classifier: None,
+                group_span: Span::new(0, 11),
+                artifact_span: Span::new(13, 22),
+                version_span: Span::new(23, 29),
+            })
+        );
+        assert_eq!(
+            Coordinate::parse("org.postgresql:postgresql:42.5.1;classifier=tests")
+                .map(|c| (c.version, c.classifier)),
+            Some(("42.5.1".to_string(), Some("tests".to_string())))
+        );
+        assert_eq!(
+            Coordinate::parse("org.postgresql:postgresql:42.5.1").map(|c| c.cross),
+            Some(CrossVersion::Disabled)
+        );
+        assert_eq!(
+            Coordinate::parse("com.lihaoyi::scalatags::0.12.0").map(|c| c.cross),
+            Some(CrossVersion::Platform)
+        );
+        assert_eq!(
+            Coordinate::parse("org.typelevel:::kind-projector:0.13.3").map(|c| c.cross),
+            Some(CrossVersion::Full)
+        );
+        assert_eq!(
+            Coordinate::parse("org.scalameta:::munit::1.0.0").map(|c| c.version_span),
+            Some(Span::new(23, 28))
+        );
+        assert_eq!(Coordinate::parse("com.lihaoyi::scalatags"), None);
+    }
+}
@@ -0,0 +1,335 @@
+use std::path::{Path, PathBuf};
+
+use tree_sitter::{Node, Query, QueryCursor};
+
+use super::{
+    coordinate::Coordinate, extract_text, parse_select, Dependency, Scope, Span, SymbolTable,
+    WithLocation,
+};
+use crate::{
+    dependency_resolver::{CoordinateLocation, Location},
+    model::{Artifact, CrossVersion, DependencyKind, Group, Version},
+};
+
+/// Parses `ivy"..."` and `mvn"..."` dependency strings.
+pub fn parse_mill_dependencies(
+    source: &Path,
+    code: &str,
+    node: &Node,
+    val_defs: &SymbolTable,
+) -> Vec<Dependency> {
+    let query = r#"
+    (interpolated_string_expression
+        interpolator: (identifier) @interpolator
+        (interpolated_string) @coordinate
+    )
+    "#;
+
+    let mut query_cursor = QueryCursor::new();
+    let query = Query::new(&tree_sitter_scala::language(), query).unwrap();
+    let matches = query_cursor.matches(&query, *node, code.as_bytes());
+
+    matches
+        .filter_map(|m| {
+            let mut interpolator_node = None;
+            let mut coordinate_node = None;
+            for capture in m.captures.iter() {
+                match query.capture_names()[capture.index as usize] {
+                    "interpolator" => interpolator_node = Some(capture.node),
+                    "coordinate" => coordinate_node = Some(capture.node),
+                    _ => {}
+                }
+            }
+
+            let interpolator = extract_text(interpolator_node?, code);
+            if interpolator != "ivy" && interpolator != "mvn" {
+                return None;
+            }
+
+            let coordinate_node = coordinate_node?;
+            // skip the opening quote
+            let content_start = coordinate_node.start_byte() + 1;
+            let content = extract_text(coordinate_node, code);
+            let coordinate = Coordinate::parse(&content)?;
+
+            let version = if coordinate.version.starts_with('$') {
+                let val = resolve_interpolation(source, coordinate_node, code, val_defs)?;
+                WithLocation {
+                    value: Version::new(&val.value),
+                    location: val.location.clone(),
+                }
+            } else {
+                WithLocation {
+                    value: Version::new(&coordinate.version),
+                    location: Location::unquoted(
+                        PathBuf::from(source),
+                        Span::new(
+                            content_start + coordinate.version_span.start,
+                            content_start + coordinate.version_span.end,
+                        ),
+                    ),
+                }
+            };
+
+            let coordinates = CoordinateLocation::unquoted(
+                PathBuf::from(source),
+                coordinate.group_span.offset(content_start),
+                coordinate.artifact_span.offset(content_start),
+            );
+            Some(Dependency {
+                group: coordinate.group,
+                artifact: coordinate.artifact,
+                kind: DependencyKind::Library,
+                cross: coordinate.cross,
+                configuration: None,
+                classifier: coordinate.classifier,
+                version,
+                coordinates: Some(coordinates),
+            })
+        })
+        .collect()
+}
+
+/// Resolves a `$version` or `${Versions.version}` interpolation to its val definition.
+/// Only the last interpolation in the string is considered, as that is where the
+/// version lives.
+fn resolve_interpolation<'a>(
+    source: &Path,
+    node: Node,
+    code: &str,
+    val_defs: &'a SymbolTable,
+) -> Option<&'a WithLocation<String>> {
+    let mut cursor = node.walk();
+    let interpolation = node
+        .named_children(&mut cursor)
+        .filter(|child| child.kind() == "interpolation")
+        .last()?;
+    let expression = interpolation.named_child(0)?;
+    let expression = if expression.kind() == "block" {
+        expression.named_child(0)?
+    } else {
+        expression
+    };
+    val_defs.resolve(
+        &Scope::at(source, expression, code),
+        &parse_select(expression, code)?,
+    )
+}
+
+/// Finds every `def scalaVersion = "..."`, including `T("...")` and `Task("...")` wrappers.
+pub fn find_mill_scala_versions(
+    source: &Path,
+    code: &str,
+    node: &Node,
+    val_defs: &SymbolTable,
+) -> Vec<WithLocation<Version>> {
+    let query = r#"
+    (function_definition
+        name: (identifier) @name
+        body: (_) @body
+        (#eq? @name "scalaVersion")
+    )
+    "#;
+
+    let mut query_cursor = QueryCursor::new();
+    let query = Query::new(&tree_sitter_scala::language(), query).unwrap();
+    let matches = query_cursor.matches(&query, *node, code.as_bytes());
+
+    matches
+        .filter_map(|m| {
+            let body = m
+                .captures
+                .iter()
+                .find(|c| query.capture_names()[c.index as usize] == "body")?
+                .node;
+            let body = match body.kind() {
+                "call_expression" => body.child_by_field_name("arguments")?.named_child(0)?,
+                _ => body,
+            };
+
+            match body.kind() {
+                "string" => Some(WithLocation {
+                    value: Version::new(&extract_text(body, code)),
+                    location: Location::new(
+                        PathBuf::from(source),
+                        Span::new(body.start_byte(), body.end_byte()),
+                    ),
+                }),
+                _ => {
+                    let val = val_defs
+                        .resolve(&Scope::at(source, body, code), &parse_select(body, code)?)?;
+                    Some(WithLocation {
+                        value: Version::new(&val.value),
+                        location: val.location.clone(),
+                    })
+                }
+            }
+        })
+        .collect()
+}
+
+/// Reads the Mill version pinned in a `.mill-version` file.
+pub fn get_mill_version(source: &Path, code: &str) -> Option<Dependency> {
+    let version = code.trim();
+    if version.is_empty() {
+        return None;
+    }
+    let start = code.find(version)?;
+
+    Some(Dependency {
+        group: Group::new("com.lihaoyi"),
+        artifact: Artifact::new("mill-dist"),
+        kind: DependencyKind::Toolchain,
+        cross: CrossVersion::Disabled,
+        configuration: None,
+        classifier: None,
+        version: WithLocation {
+            value: Version::new(version),
+            location: Location::unquoted(
+                PathBuf::from(source),
+                Span::new(start, start + version.len()),
+            ),
+        },
+        coordinates: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{parse_tree, DependencyParser};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_mill_dependencies() {
+        let code = r#"
+import mill._, scalalib._
+
+val zioVersion = "2.0.0"
+
+object foo extends ScalaModule {
+  def scalaVersion = "3.3.1"
+  def ivyDeps = Agg(
+    ivy"com.lihaoyi::scalatags:0.12.0",
+    mvn"dev.zio::zio:${zioVersion}",
+    ivy"org.postgresql:postgresql:42.5.1",
+    ivy"not a dependency"
+  )
+}
+"#;
+
+        let source = PathBuf::from("build.mill");
+        let mut parser = DependencyParser::new();
+        parser.parse_val_defs(&source, code);
+        parser.parse_mill_dependencies(&source, code);
+
+        let expected_dependencies = vec![
+            Dependency {
+                group: Group::new("com.lihaoyi"),
+                artifact: Artifact::new("scalatags"),
+                kind: DependencyKind::Library,
+                cross: CrossVersion::Binary,
+                configuration: None,
+                classifier: None,
+                version: WithLocation {
+                    value: Version::new("0.12.0"),
+                    location: Location::unquoted(source.clone(), Span::new(168, 174)),
+                },
+                coordinates: Some(CoordinateLocation::unquoted(
+                    source.clone(),
+                    Span::new(145, 156),
+                    Span::new(158, 167),
+                )),
+            },
+            Dependency {
+                group: Group::new("dev.zio"),
+                artifact: Artifact::new("zio"),
+                kind: DependencyKind::Library,
+                cross: CrossVersion::Binary,
+                configuration: None,
+                classifier: None,
+                version: WithLocation {
+                    value: Version::new("2.0.0"),
+                    location: Location::new(source.clone(), Span::new(45, 52)),
+                },
+                coordinates: Some(CoordinateLocation::unquoted(
+                    source.clone(),
+                    Span::new(185, 192),
+                    Span::new(194, 197),
+                )),
+            },
+            Dependency {
+                group: Group::new("org.postgresql"),
+                artifact: Artifact::new("postgresql"),
+                kind: DependencyKind::Library,
+                cross: CrossVersion::Disabled,
+                configuration: None,
+                classifier: None,
+                version: WithLocation {
+                    value: Version::new("42.5.1"),
+                    location: Location::unquoted(source.clone(), Span::new(248, 254)),
+                },
+                coordinates: Some(CoordinateLocation::unquoted(
+                    source.clone(),
+                    Span::new(222, 236),
+                    Span::new(237, 247),
+                )),
+            },
+            Dependency {
+                group: Group::new("org.scala-lang"),
+                artifact: Artifact::new("scala3-library_3"),
+                kind: DependencyKind::Toolchain,
+                cross: CrossVersion::Disabled,
+                configuration: None,
+                classifier: None,
+                version: WithLocation {
+                    value: Version::new("3.3.1"),
+                    location: Location::new(source.clone(), Span::new(108, 115)),
+                },
+                coordinates: None,
+            },
+        ];
+        assert_eq!(parser.dependencies, expected_dependencies);
+    }
+
+    #[test]
+    fn test_mill_scala_version_wrapped_in_task() {
+        let code = r#"
+object foo extends ScalaModule {
+  override def scalaVersion = T("2.13.12")
+}
+"#;
+        let source = PathBuf::from("build.sc");
+        let tree = parse_tree(code);
+        let versions =
+            find_mill_scala_versions(&source, code, &tree.root_node(), &SymbolTable::new());
+        assert_eq!(
+            versions,
+            vec![WithLocation {
+                value: Version::new("2.13.12"),
+                location: Location::new(source.clone(), Span::new(66, 75)),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_get_mill_version() {
+        let source = PathBuf::from(".mill-version");
+        assert_eq!(
+            get_mill_version(&source, "0.11.7\n"),
+            Some(Dependency {
+                group: Group::new("com.lihaoyi"),
+                artifact: Artifact::new("mill-dist"),
+                kind: DependencyKind::Toolchain,
+                cross: CrossVersion::Disabled,
+                configuration: None,
+                classifier: None,
+                version: WithLocation {
+                    value: Version::new("0.11.7"),
+                    location: Location::unquoted(source.clone(), Span::new(0, 6)),
+                },
+                coordinates: None,
+            })
+        );
+    }
+}
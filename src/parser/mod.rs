@@ -1,14 +1,23 @@
+pub mod coordinate;
+pub mod mill;
+pub mod platform;
+pub mod resolvers;
+pub mod scala_cli;
 pub mod span;
+pub mod symbols;
 pub use self::span::{Span, WithSpan};
+pub use self::symbols::{Scope, SymbolTable};
 
 use crate::{
-    dependency_resolver::Location,
-    model::{Artifact, Group, Version},
+    dependency_resolver::{CoordinateLocation, Location},
+    model::{
+        Artifact, CrossVersion, DependencyKey, DependencyKind, Group, Platform, Repository, Version,
+    },
 };
 
 use std::{
-    collections::HashMap,
     path::{Path, PathBuf},
+    sync::LazyLock,
 };
 use tree_sitter::{Node, Query, QueryCursor, Tree};
 
@@ -24,12 +33,44 @@ pub struct WithLocation<T> {
 pub struct Dependency {
     pub group: Group,
     pub artifact: Artifact,
+    pub kind: DependencyKind,
+    pub cross: CrossVersion,
+    /// The configuration it is scoped to, e.g. `Test` or `"provided"`.
+    pub configuration: Option<String>,
+    /// The classifier of the artifact, e.g. `tests`.
+    pub classifier: Option<String>,
     pub version: WithLocation<Version>,
+    /// Where the group and artifact are written, if they are written out literally.
+    pub coordinates: Option<CoordinateLocation>,
+}
+
+impl Dependency {
+    /// What the dependency is tracked by. The Scala versions of a cross-built
+    /// project are tracked per `major.minor` line, e.g. 2.13 or the 3.3 LTS, so
+    /// that each is updated on its own.
+    pub fn key(&self) -> DependencyKey {
+        let key = DependencyKey::new(self.group.clone(), self.artifact.clone());
+        match self.version.value.line() {
+            Some(line)
+                if self.kind == DependencyKind::Toolchain
+                    && self.group.value == "org.scala-lang"
+                    && (self.artifact.value == "scala-library"
+                        || self.artifact.value == "scala3-library_3") =>
+            {
+                key.with_line(&line)
+            }
+            _ => key,
+        }
+    }
 }
 
 pub struct DependencyParser {
-    pub val_defs: HashMap<String, WithLocation<String>>,
+    pub val_defs: SymbolTable,
     pub dependencies: Vec<Dependency>,
+    /// The platforms targeted by the parsed build files.
+    pub platforms: Vec<Platform>,
+    /// The repositories declared by the parsed build files.
+    pub repositories: Vec<Repository>,
 }
 
 impl Default for DependencyParser {
@@ -41,15 +82,17 @@ impl Default for DependencyParser {
 impl DependencyParser {
     pub fn new() -> Self {
         Self {
-            val_defs: HashMap::new(),
+            val_defs: SymbolTable::new(),
             dependencies: Vec::new(),
+            platforms: Vec::new(),
+            repositories: Vec::new(),
         }
     }
 
     pub fn parse_val_defs(&mut self, source: &Path, code: &str) {
         let tree = parse_tree(code);
         let root_node = tree.root_node();
-        parse_vals(source, root_node, code, &mut self.val_defs);
+        self.val_defs.parse(source, root_node, code);
     }
 
     pub fn parse_dependencies(&mut self, source: &Path, code: &str) {
@@ -57,6 +100,52 @@ impl DependencyParser {
         let root_node = tree.root_node();
         let dependencies = parse_dependencies(source, code, &root_node, &self.val_defs);
         self.dependencies.extend(dependencies);
+        self.platforms.extend(platform::find_platforms(code));
+        self.repositories
+            .extend(resolvers::find_repositories(code, &root_node));
+    }
+
+    /// Parses Mill's `ivy"..."` dependencies and `def scalaVersion` definitions.
+    pub fn parse_mill_dependencies(&mut self, source: &Path, code: &str) {
+        let tree = parse_tree(code);
+        let root_node = tree.root_node();
+        let dependencies = mill::parse_mill_dependencies(source, code, &root_node, &self.val_defs);
+        self.dependencies.extend(dependencies);
+        let scala_versions =
+            mill::find_mill_scala_versions(source, code, &root_node, &self.val_defs);
+        self.dependencies
+            .extend(scala_versions.into_iter().map(scala_library));
+        self.platforms.extend(platform::find_platforms(code));
+        self.repositories
+            .extend(resolvers::find_repositories(code, &root_node));
+    }
+
+    /// Parses the Scala versions of an sbt build, i.e. `scalaVersion` and
+    /// `crossScalaVersions`.
+    pub fn parse_scala_versions(&mut self, source: &Path, code: &str) {
+        let scala_versions = find_scala_versions(source, code, &self.val_defs);
+        self.dependencies
+            .extend(scala_versions.into_iter().map(scala_library));
+    }
+
+    /// Parses scala-cli `//> using` directives.
+    pub fn parse_using_directives(&mut self, source: &Path, code: &str) {
+        self.dependencies
+            .extend(scala_cli::parse_using_directives(source, code));
+        self.platforms.extend(platform::find_platforms(code));
+        self.repositories.extend(scala_cli::find_repositories(code));
+    }
+
+    /// The `//> using dep` directives of a script kept inside an sbt or Mill
+    /// build. Its `using scala` directive describes the script rather than the
+    /// build, so it is left out of the build's Scala versions.
+    pub fn parse_script_directives(&mut self, source: &Path, code: &str) {
+        self.dependencies.extend(
+            scala_cli::parse_using_directives(source, code)
+                .into_iter()
+                .filter(|dependency| dependency.kind != DependencyKind::Toolchain),
+        );
+        self.repositories.extend(scala_cli::find_repositories(code));
     }
 }
 
@@ -68,14 +157,12 @@ fn extract_text(node: Node, code: &str) -> String {
         .to_string()
 }
 
-// TODO: Use tree-sitter Query
 fn parse_val(source: &Path, node: Node, code: &str) -> Option<(String, WithLocation<String>)> {
-    let mut cursor = node.walk();
-    let mut children = node.named_children(&mut cursor);
-
-    // drop until we get to the identifier, this handles "lazy" vals (dropping the modifier)
-    let ident_node = children.find(|node| node.kind() == "identifier")?;
-    let rhs_node = children.next()?;
+    // by field, which skips the modifiers of `lazy val` and the type of `val a: String`
+    let ident_node = node
+        .child_by_field_name("pattern")
+        .filter(|pattern| pattern.kind() == "identifier")?;
+    let rhs_node = node.child_by_field_name("value")?;
 
     let ident = extract_text(ident_node, code);
     let rhs = extract_text(rhs_node, code);
@@ -90,27 +177,23 @@ fn parse_val(source: &Path, node: Node, code: &str) -> Option<(String, WithLocat
     ))
 }
 
-fn extract_vals(source: &Path, node: Node, code: &str) -> HashMap<String, WithLocation<String>> {
-    let mut vals = HashMap::new();
-    parse_vals(source, node, code, &mut vals);
-    vals
-}
-
-fn parse_vals(
-    source: &Path,
-    node: Node,
-    code: &str,
-    vals: &mut HashMap<String, WithLocation<String>>,
-) {
-    if node.kind() == "val_definition" {
-        if let Some((name, value_with_position)) = parse_val(source, node, code) {
-            vals.insert(name, value_with_position);
-            return;
-        }
-    }
-    for child in node.named_children(&mut node.walk()) {
-        parse_vals(source, child, code, vals);
+fn parse_def(source: &Path, node: Node, code: &str) -> Option<(String, WithLocation<String>)> {
+    let ident_node = node.child_by_field_name("name")?;
+    let rhs_node = node.child_by_field_name("body")?;
+    if rhs_node.kind() != "string" {
+        return None;
     }
+
+    Some((
+        extract_text(ident_node, code),
+        WithLocation {
+            value: extract_text(rhs_node, code),
+            location: Location::new(
+                PathBuf::from(source),
+                Span::new(rhs_node.start_byte(), rhs_node.end_byte()),
+            ),
+        },
+    ))
 }
 
 fn parse_tree(code: &str) -> Tree {
@@ -122,60 +205,227 @@ fn parse_tree(code: &str) -> Tree {
     parser.parse(code, None).unwrap()
 }
 
-/// find the Scala version, as defined in the `scalaVersion := "..."` infix declaration
-/// TODO: Use tree-sitter
-pub fn find_scala_version(
+/// Every Scala version the build is compiled with: each `scalaVersion := ...` and
+/// each element of `crossScalaVersions := Seq(...)`, however they are scoped, e.g.
+/// `ThisBuild / scalaVersion`, `scalaVersion in ThisBuild` or inside
+/// `inThisBuild(...)`. Each has its own location, following references to vals;
+/// a version written once but referenced from several places is found once.
+pub fn find_scala_versions(
     source: &Path,
     code: &str,
-    val_defs: &HashMap<String, WithLocation<String>>,
-) -> Option<WithLocation<Version>> {
-    let scala_version_pattern = r#"scalaVersion\s*:=\s*("([^"]+)"|[a-zA-Z_][a-zA-Z0-9_]*)"#;
-    let re = Regex::new(scala_version_pattern).unwrap();
-
-    if let Some(captures) = re.captures(code) {
-        let version_or_identifier = captures.get(1)?.as_str();
-        let position = Span::new(captures.get(1)?.start(), captures.get(1)?.end());
-
-        if version_or_identifier.starts_with('"') && version_or_identifier.ends_with('"') {
-            let version_str = version_or_identifier.trim_matches('"');
-            return Some(WithLocation {
-                value: Version::new(version_str),
-                location: Location::new(PathBuf::from(source), position),
-            });
-        } else if let Some(val) = val_defs.get(version_or_identifier) {
-            return Some(WithLocation {
-                value: Version::new(&val.value),
-                location: val.location.clone(),
-            });
+    val_defs: &SymbolTable,
+) -> Vec<WithLocation<Version>> {
+    let query = r#"
+    (infix_expression
+        left: [
+            (identifier) @key
+            (infix_expression
+                left: (identifier)
+                operator: (operator_identifier) @scope
+                right: (identifier) @key
+            )
+            (infix_expression
+                left: (identifier) @key
+                operator: (identifier) @scope
+                right: (identifier)
+            )
+        ]
+        operator: (operator_identifier) @operator
+        right: (_) @value
+    )
+    "#;
+
+    let tree = parse_tree(code);
+    let mut query_cursor = QueryCursor::new();
+    let query = Query::new(&tree_sitter_scala::language(), query).unwrap();
+    let matches = query_cursor.matches(&query, tree.root_node(), code.as_bytes());
+
+    let mut versions: Vec<WithLocation<Version>> = Vec::new();
+    for m in matches {
+        let mut key = None;
+        let mut scope = None;
+        let mut operator = None;
+        let mut value = None;
+        for capture in m.captures {
+            let text = extract_text(capture.node, code);
+            match query.capture_names()[capture.index as usize] {
+                "key" => key = Some(text),
+                "scope" => scope = Some(text),
+                "operator" => operator = Some(text),
+                "value" => value = Some(capture.node),
+                _ => {}
+            }
+        }
+        let (Some(key), Some(operator), Some(value)) = (key, operator, value) else {
+            continue;
+        };
+        // `ThisBuild / scalaVersion` or `scalaVersion in ThisBuild`
+        if scope.is_some_and(|scope| scope != "/" && scope != "in") {
+            continue;
+        }
+        let found = match (key.as_str(), operator.as_str()) {
+            ("scalaVersion", ":=") | ("crossScalaVersions", ":=" | "++=" | "+=") => {
+                scala_versions_in(source, code, 0, None, value, val_defs, 0)
+            }
+            _ => continue,
+        };
+        for version in found {
+            if !versions
+                .iter()
+                .any(|found| found.location == version.location)
+            {
+                versions.push(version);
+            }
         }
     }
-
-    None
+    versions
 }
 
-pub fn get_scala_version_from_build_sbt(source: &Path, code: &str) -> Option<Dependency> {
-    let tree = parse_tree(code);
-    let root_node = tree.root_node();
-
-    let vals = extract_vals(source, root_node, code);
-    let scala_version = find_scala_version(source, code, &vals)?;
-    let artifact_name = if scala_version.value.major() == Some(3) {
-        "scala3-library_3"
-    } else {
-        "scala-library"
+/// The Scala versions an expression stands for: a string literal, a reference to
+/// a val, or a `Seq(...)` of these, possibly wrapped in `Def.setting(...)`. A val
+/// defined as anything but a string, e.g. `List(scala212, scala213)` or
+/// `Versions.scala`, is followed into its definition, whose text starts at `offset` in its file and whose references
+/// are resolved from the `definition_scope` it was written in.
+fn scala_versions_in(
+    source: &Path,
+    code: &str,
+    offset: usize,
+    definition_scope: Option<&Scope>,
+    node: Node,
+    val_defs: &SymbolTable,
+    depth: usize,
+) -> Vec<WithLocation<Version>> {
+    // guards against vals that refer to themselves
+    if depth > 8 {
+        return Vec::new();
+    }
+    let recurse = |node: Node| {
+        scala_versions_in(
+            source,
+            code,
+            offset,
+            definition_scope,
+            node,
+            val_defs,
+            depth + 1,
+        )
     };
+
+    match node.kind() {
+        "string" => vec![WithLocation {
+            value: Version::new(&extract_text(node, code)),
+            location: Location::new(
+                PathBuf::from(source),
+                Span::new(offset + node.start_byte(), offset + node.end_byte()),
+            ),
+        }],
+        "call_expression" => {
+            let function = node.child_by_field_name("function");
+            let arguments = node.child_by_field_name("arguments");
+            match (
+                function.map(|f| extract_text(f, code)).as_deref(),
+                arguments,
+            ) {
+                (Some("Def.setting" | "Seq" | "List" | "Vector" | "Set"), Some(arguments)) => {
+                    arguments
+                        .named_children(&mut arguments.walk())
+                        .flat_map(recurse)
+                        .collect()
+                }
+                _ => Vec::new(),
+            }
+        }
+        // `Def.setting(...).value`
+        "field_expression"
+            if node
+                .child_by_field_name("value")
+                .is_some_and(|value| value.kind() == "call_expression") =>
+        {
+            recurse(node.child_by_field_name("value").unwrap())
+        }
+        "identifier" | "field_expression" => {
+            let scope = match definition_scope {
+                Some(scope) => scope.clone(),
+                None => Scope::at(source, node, code),
+            };
+            let Some(definition) = parse_select(node, code)
+                .and_then(|reference| val_defs.resolve_definition(&scope, &reference))
+            else {
+                return Vec::new();
+            };
+            let val = &definition.value;
+            if definition.is_string {
+                return vec![WithLocation {
+                    value: Version::new(&val.value),
+                    location: val.location.clone(),
+                }];
+            }
+            let tree = parse_tree(&val.value);
+            match tree.root_node().named_child(0) {
+                Some(expression) => scala_versions_in(
+                    &val.location.path,
+                    &val.value,
+                    val.location.span.start,
+                    Some(&definition.scope),
+                    expression,
+                    val_defs,
+                    depth + 1,
+                ),
+                None => Vec::new(),
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+static SBT_VERSION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^[ \t]*sbt\.version[ \t]*[=:][ \t]*([^\s#!]+)").unwrap());
+
+/// Reads the sbt launcher version from `sbt.version=...` in `project/build.properties`.
+pub fn get_sbt_version_from_build_properties(source: &Path, code: &str) -> Option<Dependency> {
+    let version = SBT_VERSION_RE.captures(code)?.get(1)?;
+
     Some(Dependency {
+        group: Group::new("org.scala-sbt"),
+        artifact: Artifact::new("sbt"),
+        kind: DependencyKind::Toolchain,
+        cross: CrossVersion::Disabled,
+        configuration: None,
+        classifier: None,
+        version: WithLocation {
+            value: Version::new(version.as_str()),
+            location: Location::unquoted(
+                PathBuf::from(source),
+                Span::new(version.start(), version.end()),
+            ),
+        },
+        coordinates: None,
+    })
+}
+
+/// The pseudo-dependency standing in for the Scala version itself.
+pub(crate) fn scala_library(scala_version: WithLocation<Version>) -> Dependency {
+    let artifact = match scala_version.value.major() {
+        Some(3) => Artifact::new("scala3-library_3"),
+        _ => Artifact::new("scala-library"),
+    };
+    Dependency {
         group: Group::new("org.scala-lang"),
-        artifact: Artifact::new(artifact_name),
+        artifact,
+        kind: DependencyKind::Toolchain,
+        cross: CrossVersion::Disabled,
+        configuration: None,
+        classifier: None,
         version: scala_version,
-    })
+        coordinates: None,
+    }
 }
 
 pub fn parse_dependencies(
     source: &Path,
     code: &str,
     node: &Node,
-    val_defs: &HashMap<String, WithLocation<String>>,
+    val_defs: &SymbolTable,
 ) -> Vec<Dependency> {
     let query = r#"
     [
@@ -190,9 +440,9 @@ pub fn parse_dependencies(
     )
     (infix_expression
         left: (infix_expression
-            left: (infix_expression 
-                left: (identifier) 
-                operator: (operator_identifier) 
+            left: (infix_expression
+                left: [(identifier) (infix_expression)]
+                operator: (operator_identifier) @assignment
                 right: (string) @group
             )
             operator: (_) @percents
@@ -206,18 +456,20 @@ pub fn parse_dependencies(
 
     let mut query_cursor = QueryCursor::new();
     let query = Query::new(&tree_sitter_scala::language(), query).unwrap();
-    let captures = query_cursor.captures(&query, *node, code.as_bytes());
+    let matches = query_cursor.matches(&query, *node, code.as_bytes());
 
-    let dependencies: Vec<_> = captures
-        .filter_map(|(m, _)| {
+    let dependencies: Vec<_> = matches
+        .filter_map(|m| {
             let mut group_node = None;
             let mut percents_node = None;
             let mut artifact_node = None;
             let mut percent_node = None;
             let mut version_node = None;
+            let mut assignment_node = None;
 
             for capture in m.captures.iter() {
                 match query.capture_names()[capture.index as usize] {
+                    "assignment" => assignment_node = Some(capture.node),
                     "percents" => percents_node = Some(capture.node),
                     "percent" => percent_node = Some(capture.node),
                     "group" => group_node = Some(capture.node),
@@ -227,10 +479,12 @@ pub fn parse_dependencies(
                 }
             }
 
-            let percents_text = extract_text(percents_node?, code);
-            if !percents_text.chars().all(|c| c == '%') {
+            // `key += "g" %% "a" % v` or `ThisBuild / key += ...`, parsed left to right,
+            // rather than the `%` of a longer `"g" % "a" % v % "test"`
+            if assignment_node.is_some_and(|node| !extract_text(node, code).ends_with('=')) {
                 return None;
             }
+            let cross = CrossVersion::from_operator(&extract_text(percents_node?, code))?;
 
             let percent_text = extract_text(percent_node?, code);
             if !percent_text.chars().all(|c| c == '%') {
@@ -246,17 +500,10 @@ pub fn parse_dependencies(
                         Span::new(version_node.start_byte(), version_node.end_byte()),
                     ),
                 },
-                "identifier" => {
-                    let ident = extract_text(version_node, code);
-                    let val = val_defs.get(&ident)?;
-                    WithLocation {
-                        value: Version::new(&val.value),
-                        location: val.location.clone(),
-                    }
-                }
                 _ => {
-                    let ident = parse_select(version_node, code)?;
-                    let val = val_defs.get(&ident)?;
+                    let reference = parse_select(version_node, code)?;
+                    let val =
+                        val_defs.resolve(&Scope::at(source, version_node, code), &reference)?;
                     WithLocation {
                         value: Version::new(&val.value),
                         location: val.location.clone(),
@@ -264,44 +511,187 @@ pub fn parse_dependencies(
                 }
             };
 
+            let modifiers = parse_modifiers(version_node.parent()?, code);
+            let cross = modifiers
+                .cross
+                .and_then(|setting| cross.with_setting(&setting))
+                .unwrap_or(cross);
+
+            let group_node = group_node?;
+            let artifact_node = artifact_node?;
+            let kind = declaration_kind(group_node, code)?;
+            let coordinates = (artifact_node.kind() == "string").then(|| {
+                CoordinateLocation::quoted(
+                    PathBuf::from(source),
+                    Span::new(group_node.start_byte(), group_node.end_byte()),
+                    Span::new(artifact_node.start_byte(), artifact_node.end_byte()),
+                )
+            });
             Some(Dependency {
-                group: Group::new(&extract_text(group_node?, code)),
-                artifact: Artifact::new(&extract_text(artifact_node?, code)),
+                group: Group::new(&extract_text(group_node, code)),
+                artifact: Artifact::new(&extract_text(artifact_node, code)),
+                kind,
+                cross,
+                configuration: modifiers.configuration,
+                classifier: modifiers.classifier,
                 version,
+                coordinates,
             })
         })
         .collect();
     dependencies
 }
 
-// Versions.version -> version
-// Thing.Other.version -> version
-// version -> version
-fn parse_select(node: Node, code: &str) -> Option<String> {
-    let mut cursor = node.walk();
-    let mut children = node.named_children(&mut cursor);
+/// What a `"g" %% "a" % v` module is wrapped in, e.g. `% Test`, `classifier "tests"`
+/// or `.cross(CrossVersion.for3Use2_13)`.
+#[derive(Debug, Default, PartialEq)]
+struct Modifiers {
+    configuration: Option<String>,
+    classifier: Option<String>,
+    /// The `.cross(...)` setting, e.g. `CrossVersion.full`.
+    cross: Option<String>,
+}
+
+impl Modifiers {
+    fn apply(&mut self, modifier: &str, argument: Option<Node>, code: &str) {
+        // `classifier("tests")` in infix position parses as a parenthesized expression
+        let argument = argument.map(|argument| match argument.kind() {
+            "parenthesized_expression" => argument.named_child(0).unwrap_or(argument),
+            _ => argument,
+        });
+        let argument = argument.map(|argument| extract_text(argument, code));
+        match modifier {
+            "%" => self.configuration = argument,
+            "classifier" => self.classifier = argument,
+            "cross" => self.cross = argument,
+            // e.g. `exclude`, `excludeAll`, `withSources` or `intransitive`, which
+            // don't change how the dependency is resolved
+            _ => {}
+        }
+    }
+}
 
-    // Check if the node is an identifier
-    if node.kind() == "identifier" {
-        return Some(extract_text(node, code));
+/// Walks out of a module through its modifiers, in both their infix form,
+/// `module % Test classifier "tests"`, and their method form, `(module).cross(...)`.
+fn parse_modifiers(module: Node, code: &str) -> Modifiers {
+    let mut modifiers = Modifiers::default();
+    let mut node = module;
+    while let Some(parent) = node.parent() {
+        match parent.kind() {
+            "parenthesized_expression" => {}
+            "infix_expression" if parent.child_by_field_name("left") == Some(node) => {
+                let Some(operator) = parent.child_by_field_name("operator") else {
+                    break;
+                };
+                let argument = parent.child_by_field_name("right");
+                modifiers.apply(&extract_text(operator, code), argument, code);
+            }
+            "field_expression" if parent.child_by_field_name("value") == Some(node) => {
+                let Some(method) = parent.child_by_field_name("field") else {
+                    break;
+                };
+                let call = parent.parent().filter(|call| {
+                    call.kind() == "call_expression"
+                        && call.child_by_field_name("function") == Some(parent)
+                });
+                let argument = call
+                    .and_then(|call| call.child_by_field_name("arguments"))
+                    .and_then(|arguments| arguments.named_child(0));
+                modifiers.apply(&extract_text(method, code), argument, code);
+                if let Some(call) = call {
+                    node = call;
+                    continue;
+                }
+            }
+            _ => break,
+        }
+        node = parent;
     }
+    modifiers
+}
 
-    // Iterate through the children to find the last identifier
-    let mut last_identifier = None;
-    while let Some(child) = children.next() {
-        if child.kind() == "identifier" {
-            last_identifier = Some(child);
+/// Classifies a dependency by where it is declared: in `addSbtPlugin(...)`,
+/// `addCompilerPlugin(...)` or `compilerPlugin(...)`, or by the setting it is
+/// added to, e.g. `dependencyOverrides += ...`. `None` for `excludeDependencies`,
+/// whose entries aren't dependencies at all.
+fn declaration_kind(node: Node, code: &str) -> Option<DependencyKind> {
+    let mut current = node.parent();
+    while let Some(node) = current {
+        match node.kind() {
+            "call_expression" => {
+                let function = node
+                    .child_by_field_name("function")
+                    .map(|f| extract_text(f, code));
+                match function.as_deref() {
+                    Some("addSbtPlugin") => return Some(DependencyKind::SbtPlugin),
+                    Some("addCompilerPlugin" | "compilerPlugin") => {
+                        return Some(DependencyKind::CompilerPlugin)
+                    }
+                    _ => {}
+                }
+            }
+            "infix_expression" => match setting_key(node, code).as_deref() {
+                Some("libraryDependencies") => return Some(DependencyKind::Library),
+                Some("dependencyOverrides") => return Some(DependencyKind::Override),
+                Some("excludeDependencies") => return None,
+                _ => {}
+            },
+            _ => {}
         }
+        current = node.parent();
     }
+    Some(DependencyKind::Library)
+}
 
-    // Extract the text of the last identifier if it exists
-    last_identifier.map(|ident_node| extract_text(ident_node, code))
+/// The setting an infix expression assigns or appends to, e.g. `libraryDependencies`
+/// of `ThisBuild / libraryDependencies += ...` or `libraryDependencies in Test += ...`.
+fn setting_key(node: Node, code: &str) -> Option<String> {
+    let operator = extract_text(node.child_by_field_name("operator")?, code);
+    if !matches!(operator.as_str(), ":=" | "+=" | "++=" | "-=" | "--=") {
+        return None;
+    }
+    let left = node.child_by_field_name("left")?;
+    let key = match left.kind() {
+        "identifier" => left,
+        "infix_expression" => {
+            let scope = extract_text(left.child_by_field_name("operator")?, code);
+            match scope.as_str() {
+                "/" => left.child_by_field_name("right")?,
+                "in" => left.child_by_field_name("left")?,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    (key.kind() == "identifier").then(|| extract_text(key, code))
+}
+
+// Versions.version -> Versions.version
+// Thing.Other.version -> Thing.Other.version
+// version -> version
+fn parse_select(node: Node, code: &str) -> Option<String> {
+    match node.kind() {
+        "identifier" => Some(extract_text(node, code)),
+        "field_expression" => {
+            let value = parse_select(node.child_by_field_name("value")?, code)?;
+            let field = extract_text(node.child_by_field_name("field")?, code);
+            Some(format!("{}.{}", value, field))
+        }
+        _ => None,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+
+    fn extract_vals(source: &Path, node: Node, code: &str) -> SymbolTable {
+        let mut vals = SymbolTable::new();
+        vals.parse(source, node, code);
+        vals
+    }
 
     #[test]
     fn test_scala_parser() {
@@ -335,55 +725,240 @@ mod tests {
             Dependency {
                 group: Group::new("io.github.kitlangton"),
                 artifact: Artifact::new("neotype"),
+                kind: DependencyKind::Library,
+                cross: CrossVersion::Binary,
+                configuration: None,
+                classifier: None,
                 version: WithLocation {
                     value: Version::new("0.1.0"),
                     location: Location::new(source.clone(), Span::new(89, 96)),
                 },
+                coordinates: Some(CoordinateLocation::quoted(
+                    source.clone(),
+                    Span::new(155, 177),
+                    Span::new(181, 190),
+                )),
             },
             Dependency {
                 group: Group::new("dev.zio"),
                 artifact: Artifact::new("zio"),
+                kind: DependencyKind::Library,
+                cross: CrossVersion::Binary,
+                configuration: None,
+                classifier: None,
                 version: WithLocation {
                     value: Version::new("2.0.0"),
                     location: Location::new(source.clone(), Span::new(242, 249)),
                 },
+                coordinates: Some(CoordinateLocation::quoted(
+                    source.clone(),
+                    Span::new(221, 230),
+                    Span::new(234, 239),
+                )),
             },
             Dependency {
                 group: Group::new("org.postgresql"),
                 artifact: Artifact::new("postgresql"),
+                kind: DependencyKind::Library,
+                cross: CrossVersion::Disabled,
+                configuration: None,
+                classifier: None,
                 version: WithLocation {
                     value: Version::new("42.5.1"),
                     location: Location::new(source.clone(), Span::new(295, 303)),
                 },
+                coordinates: Some(CoordinateLocation::quoted(
+                    source.clone(),
+                    Span::new(261, 277),
+                    Span::new(280, 292),
+                )),
             },
             Dependency {
                 group: Group::new("io.github.kitlangton"),
                 artifact: Artifact::new("animus"),
+                kind: DependencyKind::Library,
+                cross: CrossVersion::Disabled,
+                configuration: None,
+                classifier: None,
                 version: WithLocation {
                     value: Version::new("0.4.0"),
                     location: Location::new(source.clone(), Span::new(29, 36)),
                 },
+                coordinates: Some(CoordinateLocation::quoted(
+                    source.clone(),
+                    Span::new(315, 337),
+                    Span::new(340, 348),
+                )),
             },
             Dependency {
                 group: Group::new("example"),
                 artifact: Artifact::new("example"),
+                kind: DependencyKind::Library,
+                cross: CrossVersion::Binary,
+                configuration: None,
+                classifier: None,
                 version: WithLocation {
                     value: Version::new("0.0.1"),
                     location: Location::new(source.clone(), Span::new(423, 430)),
                 },
+                coordinates: Some(CoordinateLocation::quoted(
+                    source.clone(),
+                    Span::new(398, 407),
+                    Span::new(411, 420),
+                )),
             },
             Dependency {
                 group: Group::new("dev.zio"),
                 artifact: Artifact::new("zio-test"),
+                kind: DependencyKind::Library,
+                cross: CrossVersion::Binary,
+                configuration: Some("Test".to_string()),
+                classifier: None,
                 version: WithLocation {
                     value: Version::new("2.0.0"),
                     location: Location::new(source.clone(), Span::new(544, 551)),
                 },
+                coordinates: Some(CoordinateLocation::quoted(
+                    source.clone(),
+                    Span::new(518, 527),
+                    Span::new(531, 541),
+                )),
             },
         ];
         assert_eq!(parser.dependencies, expected_dependencies);
     }
 
+    #[test]
+    fn test_cross_version_operators() {
+        let code = r#"
+        libraryDependencies ++= Seq(
+          "dev.zio" %%% "zio" % "2.0.0",
+          "dev.zio" %% "zio-json" % "0.6.0",
+          "org.postgresql" % "postgresql" % "42.5.1",
+          "dev.zio" %%%% "zio-nope" % "1.0.0"
+        )
+        "#;
+
+        let source = PathBuf::from("build.sbt");
+        let mut parser = DependencyParser::new();
+        parser.parse_dependencies(&source, code);
+
+        let crosses: Vec<_> = parser
+            .dependencies
+            .iter()
+            .map(|d| (d.artifact.value.as_str(), d.cross))
+            .collect();
+        assert_eq!(
+            crosses,
+            vec![
+                ("zio", CrossVersion::Platform),
+                ("zio-json", CrossVersion::Binary),
+                ("postgresql", CrossVersion::Disabled),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wrapped_dependencies() {
+        let code = r#"
+        libraryDependencies ++= Seq(
+          ("org.a" %% "a" % "1.0").cross(CrossVersion.for3Use2_13),
+          "org.b" %% "b" % "1.0" % Test classifier "tests",
+          "org.c" %% "c" % "1.0" exclude("org.x", "x"),
+          ("org.d" %%% "d" % "1.0").exclude("org.x", "x").cross(CrossVersion.for3Use2_13),
+          "org.e" % "e" % "1.0" withSources(),
+          ("org.f" %% "f" % "1.0" % "provided").excludeAll(ExclusionRule("org.x")),
+          "org.g" % "g" % "1.0" % "it,test" cross CrossVersion.full,
+          ("org.h" %% "h" % "1.0").classifier("natives").withSources()
+        )
+        libraryDependencies += "org.i" %% "i" % "1.0" % Test classifier("tests")
+        "#;
+
+        let mut parser = DependencyParser::new();
+        parser.parse_dependencies(&PathBuf::from("build.sbt"), code);
+        let dependencies: Vec<_> = parser
+            .dependencies
+            .iter()
+            .map(|d| {
+                (
+                    d.artifact.value.as_str(),
+                    d.cross,
+                    d.configuration.as_deref(),
+                    d.classifier.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            dependencies,
+            vec![
+                (
+                    "a",
+                    CrossVersion::For3Use2_13 { platform: false },
+                    None,
+                    None
+                ),
+                ("b", CrossVersion::Binary, Some("Test"), Some("tests")),
+                ("c", CrossVersion::Binary, None, None),
+                (
+                    "d",
+                    CrossVersion::For3Use2_13 { platform: true },
+                    None,
+                    None
+                ),
+                ("e", CrossVersion::Disabled, None, None),
+                ("f", CrossVersion::Binary, Some("provided"), None),
+                ("g", CrossVersion::Full, Some("it,test"), None),
+                ("h", CrossVersion::Binary, None, Some("natives")),
+                ("i", CrossVersion::Binary, Some("Test"), Some("tests")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_declaration_kinds() {
+        let code = r#"
+        val kindProjector = "0.13.3"
+        val sharedDependencies = Def.setting(Seq(
+          "org.typelevel" %%% "cats-core" % "2.10.0"
+        ))
+        lazy val core = crossProject(JVMPlatform, JSPlatform).settings(
+          addCompilerPlugin("org.typelevel" % "kind-projector" % kindProjector cross CrossVersion.full),
+          libraryDependencies ++= sharedDependencies.value,
+          libraryDependencies += compilerPlugin("com.olegpy" %% "better-monadic-for" % "0.3.1"),
+          ThisBuild / dependencyOverrides += "org.scala-lang.modules" %% "scala-xml" % "2.2.0",
+          excludeDependencies += "org.slf4j" % "slf4j-log4j12" % "1.7.36"
+        )
+        dependencyOverrides ++= Seq("com.google.guava" % "guava" % "32.1.3-jre")
+        "#;
+
+        let mut parser = DependencyParser::new();
+        parser.parse_val_defs(&PathBuf::from("build.sbt"), code);
+        parser.parse_dependencies(&PathBuf::from("build.sbt"), code);
+        let kinds: Vec<_> = parser
+            .dependencies
+            .iter()
+            .map(|d| (d.artifact.value.as_str(), d.kind, d.cross))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("cats-core", DependencyKind::Library, CrossVersion::Platform),
+                (
+                    "kind-projector",
+                    DependencyKind::CompilerPlugin,
+                    CrossVersion::Full
+                ),
+                (
+                    "better-monadic-for",
+                    DependencyKind::CompilerPlugin,
+                    CrossVersion::Binary
+                ),
+                ("scala-xml", DependencyKind::Override, CrossVersion::Binary),
+                ("guava", DependencyKind::Override, CrossVersion::Disabled),
+            ]
+        );
+    }
+
     #[test]
     fn test_extract_vals() {
         let code = r#"
@@ -405,41 +980,45 @@ mod tests {
         let val_defs = extract_vals(&source, root_node, code);
         let expected_val_defs = HashMap::from([
             (
-                "example".to_string(),
+                "Outer.example".to_string(),
                 WithLocation {
                     value: "Hello".to_string(),
                     location: Location::new(source.clone(), Span::new(58, 65)),
                 },
             ),
             (
-                "falseExample".to_string(),
+                "Outer.falseExample".to_string(),
                 WithLocation {
                     value: "123".to_string(),
                     location: Location::new(source.clone(), Span::new(101, 104)),
                 },
             ),
             (
-                "anotherExample".to_string(),
+                "Outer.Inner.anotherExample".to_string(),
                 WithLocation {
                     value: "World".to_string(),
                     location: Location::new(source.clone(), Span::new(177, 184)),
                 },
             ),
             (
-                "yetAnotherExample".to_string(),
+                "Outer.Inner.yetAnotherExample".to_string(),
                 WithLocation {
                     value: "456".to_string(),
                     location: Location::new(source.clone(), Span::new(229, 232)),
                 },
             ),
             (
-                "complexExample".to_string(),
+                "Outer.complexExample".to_string(),
                 WithLocation {
                     value: "Hello\" + \"World".to_string(),
                     location: Location::new(source.clone(), Span::new(288, 305)),
                 },
             ),
         ]);
+        let val_defs: HashMap<String, WithLocation<String>> = val_defs
+            .iter()
+            .map(|(path, value)| (path.clone(), value.clone()))
+            .collect();
         assert_eq!(val_defs, expected_val_defs);
     }
 
@@ -454,14 +1033,14 @@ mod tests {
         let root_node = tree.root_node();
 
         let val_defs = extract_vals(&source, root_node, code);
-        let scala_version = find_scala_version(&source, code, &val_defs);
+        let scala_versions = find_scala_versions(&source, code, &val_defs);
 
         assert_eq!(
-            scala_version,
-            Some(WithLocation {
+            scala_versions,
+            vec![WithLocation {
                 value: Version::new("2.13.6"),
                 location: Location::new(source.clone(), Span::new(25, 33)),
-            })
+            }]
         );
     }
 
@@ -479,14 +1058,160 @@ mod tests {
         let root_node = tree.root_node();
 
         let val_defs = extract_vals(&source, root_node, code);
-        let scala_version = find_scala_version(&source, code, &val_defs);
+        let scala_versions = find_scala_versions(&source, code, &val_defs);
 
         assert_eq!(
-            scala_version,
-            Some(WithLocation {
+            scala_versions,
+            vec![WithLocation {
                 value: Version::new("2.12.8"),
                 location: Location::new(source.clone(), Span::new(52, 60)),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_cross_scala_versions() {
+        let code = r#"
+        val scala213 = "2.13.14"
+        ThisBuild / scalaVersion := scala213
+        crossScalaVersions := Seq(scala213, "3.3.3")
+    "#;
+
+        let source = PathBuf::from("build.sbt");
+        let tree = parse_tree(code);
+        let val_defs = extract_vals(&source, tree.root_node(), code);
+        let scala_versions = find_scala_versions(&source, code, &val_defs);
+
+        assert_eq!(
+            scala_versions,
+            vec![
+                WithLocation {
+                    value: Version::new("2.13.14"),
+                    location: Location::new(source.clone(), Span::new(24, 33)),
+                },
+                WithLocation {
+                    value: Version::new("3.3.3"),
+                    location: Location::new(source.clone(), Span::new(123, 130)),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scala_version_forms() {
+        let versions = r#"
+        object Versions {
+          val scala212: String = "2.12.19"
+          val scala213 = "2.13.14"
+          lazy val supported = List(scala212, scala213)
+        }
+        "#;
+        let build = r#"
+        // scalaVersion := "2.11.12"
+        val description = "scalaVersion := \"2.10.7\""
+        inThisBuild(List(scalaVersion := Versions.scala213))
+        ThisBuild / crossScalaVersions := Versions.supported
+        lazy val docs = project.settings(scalaVersion in ThisBuild := "3.3.3")
+        lazy val next = project.settings(scalaVersion := Def.setting("3.5.0").value)
+        "#;
+
+        let versions_path = PathBuf::from("project/Versions.scala");
+        let build_path = PathBuf::from("build.sbt");
+        let mut parser = DependencyParser::new();
+        parser.parse_val_defs(&versions_path, versions);
+        parser.parse_val_defs(&build_path, build);
+
+        let found: Vec<_> = find_scala_versions(&build_path, build, &parser.val_defs)
+            .into_iter()
+            .map(|version| {
+                let location = version.location;
+                let code = if location.path == versions_path {
+                    versions
+                } else {
+                    build
+                };
+                (
+                    version.value.to_string(),
+                    location.path,
+                    &code[location.span.start..location.span.end],
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("2.13.14".to_string(), versions_path.clone(), "\"2.13.14\""),
+                ("2.12.19".to_string(), versions_path.clone(), "\"2.12.19\""),
+                ("3.3.3".to_string(), build_path.clone(), "\"3.3.3\""),
+                ("3.5.0".to_string(), build_path.clone(), "\"3.5.0\""),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_vals_resolve_from_their_definition() {
+        let versions = r#"
+        object Versions {
+          val scala212 = "2.12.19"
+          val scala213 = "2.13.14"
+          lazy val supported = List(scala212, scala213)
+        }
+        object Legacy {
+          val scala212 = "2.12.10"
+        }
+        "#;
+        let build = r#"
+        crossScalaVersions := Versions.supported
+        "#;
+
+        let versions_path = PathBuf::from("project/Versions.scala");
+        let build_path = PathBuf::from("build.sbt");
+        let mut parser = DependencyParser::new();
+        parser.parse_val_defs(&versions_path, versions);
+        parser.parse_val_defs(&build_path, build);
+
+        let found: Vec<_> = find_scala_versions(&build_path, build, &parser.val_defs)
+            .into_iter()
+            .map(|version| version.value.to_string())
+            .collect();
+        assert_eq!(found, vec!["2.12.19", "2.13.14"]);
+    }
+
+    #[test]
+    fn test_vals_referring_to_vals_are_followed() {
+        let versions = r#"
+        object Versions {
+          val scala = "2.13.14"
+        }
+        "#;
+        let build = r#"
+        val scalaV = Versions.scala
+        val fromEnv = sys.props.getOrElse("scala", scalaV)
+        scalaVersion := scalaV
+        crossScalaVersions := Seq(fromEnv)
+        "#;
+
+        let versions_path = PathBuf::from("project/Versions.scala");
+        let build_path = PathBuf::from("build.sbt");
+        let mut parser = DependencyParser::new();
+        parser.parse_val_defs(&versions_path, versions);
+        parser.parse_val_defs(&build_path, build);
+
+        // `fromEnv` is no version, and neither are the names of the vals
+        let found: Vec<_> = find_scala_versions(&build_path, build, &parser.val_defs)
+            .into_iter()
+            .map(|version| {
+                let span = version.location.span;
+                (
+                    version.value.to_string(),
+                    version.location.path,
+                    &versions[span.start..span.end],
+                )
             })
+            .collect();
+        assert_eq!(
+            found,
+            vec![("2.13.14".to_string(), versions_path, "\"2.13.14\"")]
         );
     }
 
@@ -498,17 +1223,47 @@ mod tests {
     "#;
 
         let source = PathBuf::from("example.scala");
-        let scala_version = get_scala_version_from_build_sbt(&source, code);
+        let mut parser = DependencyParser::new();
+        parser.parse_val_defs(&source, code);
+        parser.parse_scala_versions(&source, code);
 
         assert_eq!(
-            scala_version,
-            Some(Dependency {
+            parser.dependencies,
+            vec![Dependency {
                 group: Group::new("org.scala-lang"),
                 artifact: Artifact::new("scala3-library_3"),
+                kind: DependencyKind::Toolchain,
+                cross: CrossVersion::Disabled,
+                configuration: None,
+                classifier: None,
                 version: WithLocation {
                     value: Version::new("3.4.2"),
                     location: Location::new(source.clone(), Span::new(22, 29)),
-                }
+                },
+                coordinates: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_sbt_version_from_build_properties() {
+        let code = "# the sbt launcher\nsbt.version = 1.9.9\n";
+        let source = PathBuf::from("project/build.properties");
+
+        assert_eq!(
+            get_sbt_version_from_build_properties(&source, code),
+            Some(Dependency {
+                group: Group::new("org.scala-sbt"),
+                artifact: Artifact::new("sbt"),
+                kind: DependencyKind::Toolchain,
+                cross: CrossVersion::Disabled,
+                configuration: None,
+                classifier: None,
+                version: WithLocation {
+                    value: Version::new("1.9.9"),
+                    location: Location::unquoted(source.clone(), Span::new(33, 38)),
+                },
+                coordinates: None,
             })
         );
     }
@@ -559,6 +1314,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_versions_resolved_by_qualified_path() {
+        let versions = r#"
+        object Versions { val zio = "2.1.0" }
+        object LegacyVersions { val zio = "1.0.18" }
+        "#;
+        let build = r#"
+        import LegacyVersions._
+        libraryDependencies ++= Seq(
+          "dev.zio" %% "zio" % Versions.zio,
+          "dev.zio" %% "zio-test" % zio
+        )
+        "#;
+
+        let mut parser = DependencyParser::new();
+        parser.parse_val_defs(&PathBuf::from("project/Versions.scala"), versions);
+        parser.parse_dependencies(&PathBuf::from("build.sbt"), build);
+        let versions: Vec<_> = parser
+            .dependencies
+            .iter()
+            .map(|d| (d.artifact.value.as_str(), d.version.value.to_string()))
+            .collect();
+        assert_eq!(
+            versions,
+            vec![
+                ("zio", "2.1.0".to_string()),
+                ("zio-test", "1.0.18".to_string())
+            ]
+        );
+    }
+
     #[test]
     fn test_extract_dependencies() {
         let code = r#"
@@ -0,0 +1,83 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::model::Platform;
+
+static SBT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:enablePlugins|crossProject)\s*\(([^)]*)\)").unwrap());
+static MATRIX_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\.(jvm|js|native)Platform\s*\(").unwrap());
+static MILL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(ScalaJSModule|ScalaNativeModule)\b").unwrap());
+static SCALA_CLI_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^\s*//>\s*using\s+platforms?\s+(.*)$").unwrap());
+
+/// Finds the platforms a build file targets, as declared by sbt's
+/// `enablePlugins(ScalaJSPlugin)` and `crossProject(JSPlatform, JVMPlatform)`,
+/// Mill's `ScalaJSModule` and `ScalaNativeModule`, or scala-cli's `//> using platform`.
+pub fn find_platforms(code: &str) -> Vec<Platform> {
+    let mut platforms = Vec::new();
+    for captures in SBT_RE.captures_iter(code) {
+        for name in captures[1].split(',').map(str::trim) {
+            platforms.extend(match name {
+                "ScalaJSPlugin" | "JSPlatform" => Some(Platform::Js),
+                "ScalaNativePlugin" | "NativePlatform" => Some(Platform::Native),
+                "JVMPlatform" => Some(Platform::Jvm),
+                _ => None,
+            });
+        }
+    }
+    for captures in MATRIX_RE.captures_iter(code) {
+        platforms.push(match &captures[1] {
+            "js" => Platform::Js,
+            "native" => Platform::Native,
+            _ => Platform::Jvm,
+        });
+    }
+    for captures in MILL_RE.captures_iter(code) {
+        platforms.push(match &captures[1] {
+            "ScalaJSModule" => Platform::Js,
+            _ => Platform::Native,
+        });
+    }
+    for captures in SCALA_CLI_RE.captures_iter(code) {
+        for name in captures[1].split([' ', ',']) {
+            platforms.extend(match name.trim_matches('"') {
+                "js" | "scala-js" | "scalajs" => Some(Platform::Js),
+                "native" | "scala-native" | "scalanative" => Some(Platform::Native),
+                "jvm" => Some(Platform::Jvm),
+                _ => None,
+            });
+        }
+    }
+
+    platforms.sort();
+    platforms.dedup();
+    platforms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_find_platforms() {
+        let sbt = r#"
+lazy val core = crossProject(JSPlatform, JVMPlatform)
+  .settings(libraryDependencies += "dev.zio" %%% "zio" % "2.0.0")
+
+lazy val frontend = project.enablePlugins(ScalaJSPlugin, BuildInfoPlugin)
+"#;
+        assert_eq!(find_platforms(sbt), vec![Platform::Jvm, Platform::Js]);
+
+        let mill = "object foo extends ScalaNativeModule {}";
+        assert_eq!(find_platforms(mill), vec![Platform::Native]);
+
+        let scala_cli = "//> using platform scala-js\n";
+        assert_eq!(find_platforms(scala_cli), vec![Platform::Js]);
+
+        assert_eq!(find_platforms("lazy val root = project"), vec![]);
+    }
+}
@@ -0,0 +1,103 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+use tree_sitter::{Node, Query, QueryCursor};
+
+use super::extract_text;
+use crate::model::Repository;
+
+static SONATYPE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"Resolver\.sonatype(?:Oss)?Repos?\(\s*"([\w-]+)"\s*\)"#).unwrap()
+});
+
+/// Finds the repositories a build declares, e.g. `resolvers += "Internal" at "https://..."`,
+/// `MavenRepository("Internal", "https://...")` or `Resolver.sonatypeOssRepos("snapshots")`.
+pub fn find_repositories(code: &str, node: &Node) -> Vec<Repository> {
+    let query = r#"
+    [
+    (infix_expression
+        left: [
+            (string) @name
+            ; `resolvers += "name" at "url"` parses as `(resolvers += "name") at "url"`
+            (infix_expression right: (string) @name)
+        ]
+        operator: (identifier) @operator
+        right: (string) @url
+        (#eq? @operator "at")
+    )
+    (call_expression
+        function: (identifier) @function
+        arguments: (arguments . (string) @name . (string)? @url .)
+        (#eq? @function "MavenRepository")
+    )
+    ]
+    "#;
+
+    let mut query_cursor = QueryCursor::new();
+    let query = Query::new(&tree_sitter_scala::language(), query).unwrap();
+    let matches = query_cursor.matches(&query, *node, code.as_bytes());
+
+    let mut repositories: Vec<Repository> = matches
+        .filter_map(|m| {
+            let mut name = None;
+            let mut url = None;
+            for capture in m.captures.iter() {
+                match query.capture_names()[capture.index as usize] {
+                    "name" => name = Some(extract_text(capture.node, code)),
+                    "url" => url = Some(extract_text(capture.node, code)),
+                    _ => {}
+                }
+            }
+            // Mill's `MavenRepository("https://...")` only takes the URL
+            let name = name?;
+            let url = url.unwrap_or_else(|| name.clone());
+            is_remote(&url).then(|| Repository::new(&name, &url))
+        })
+        .collect();
+
+    for captures in SONATYPE_RE.captures_iter(code) {
+        repositories.push(Repository::new(
+            &format!("sonatype-{}", &captures[1]),
+            &format!(
+                "https://oss.sonatype.org/content/repositories/{}",
+                &captures[1]
+            ),
+        ));
+    }
+
+    repositories
+}
+
+pub(crate) fn is_remote(url: &str) -> bool {
+    url.starts_with("https://") || url.starts_with("http://")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_tree;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_find_repositories() {
+        let code = r#"
+resolvers += "Internal" at "https://artifactory.example.com/maven/"
+resolvers ++= Resolver.sonatypeOssRepos("snapshots")
+resolvers += MavenRepository("Other", "https://maven.example.org")
+resolvers += Resolver.mavenLocal
+val notAResolver = "a" at "b"
+"#;
+        let tree = parse_tree(code);
+        assert_eq!(
+            find_repositories(code, &tree.root_node()),
+            vec![
+                Repository::new("Internal", "https://artifactory.example.com/maven"),
+                Repository::new("Other", "https://maven.example.org"),
+                Repository::new(
+                    "sonatype-snapshots",
+                    "https://oss.sonatype.org/content/repositories/snapshots"
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,229 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+use regex::Regex;
+
+use super::{
+    coordinate::Coordinate, resolvers::is_remote, scala_library, Dependency, Span, WithLocation,
+};
+use crate::{
+    dependency_resolver::{CoordinateLocation, Location},
+    model::{DependencyKind, Repository, Version},
+};
+
+/// Directive keys declaring dependencies. `test.` and `compileOnly.` scoped
+/// variants are accepted as well.
+const DEPENDENCY_KEYS: [&str; 6] = ["dep", "deps", "dependency", "dependencies", "lib", "libs"];
+
+static DIRECTIVE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*//>\s*using\s+([\w.\-]+)").unwrap());
+static REPOSITORY_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^\s*//>\s*using\s+repos(?:itory|itories)?\s+(.*)$").unwrap());
+/// A directive value: quoted, backquoted or bare.
+static VALUE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#""([^"]*)"|`([^`]*)`|([^\s,"`]+)"#).unwrap());
+
+/// Parses scala-cli `//> using dep ...` and `//> using scala ...` directives.
+pub fn parse_using_directives(source: &Path, code: &str) -> Vec<Dependency> {
+    let mut dependencies = Vec::new();
+    let mut line_start = 0;
+    for line in code.split_inclusive('\n') {
+        let offset = line_start;
+        line_start += line.len();
+
+        let Some(captures) = DIRECTIVE_RE.captures(line) else {
+            continue;
+        };
+        let key = captures.get(1).unwrap();
+        let is_scala = key.as_str() == "scala";
+        let is_dependency = DEPENDENCY_KEYS.contains(&unscoped(key.as_str()));
+        if !is_scala && !is_dependency {
+            continue;
+        }
+
+        let values = &line[key.end()..];
+        for value_captures in VALUE_RE.captures_iter(values) {
+            let Some(value) = (1..=3).find_map(|i| value_captures.get(i)) else {
+                continue;
+            };
+            let value_start = offset + key.end() + value.start();
+
+            if is_scala {
+                let span = Span::new(value_start, value_start + value.len());
+                dependencies.push(scala_library(WithLocation {
+                    value: Version::new(value.as_str()),
+                    location: Location::unquoted(PathBuf::from(source), span),
+                }));
+            } else if let Some(coordinate) = Coordinate::parse(value.as_str()) {
+                let span = Span::new(
+                    value_start + coordinate.version_span.start,
+                    value_start + coordinate.version_span.end,
+                );
+                let coordinates = CoordinateLocation::unquoted(
+                    PathBuf::from(source),
+                    coordinate.group_span.offset(value_start),
+                    coordinate.artifact_span.offset(value_start),
+                );
+                dependencies.push(Dependency {
+                    group: coordinate.group,
+                    artifact: coordinate.artifact,
+                    kind: DependencyKind::Library,
+                    cross: coordinate.cross,
+                    configuration: configuration(key.as_str()),
+                    classifier: coordinate.classifier,
+                    version: WithLocation {
+                        value: Version::new(&coordinate.version),
+                        location: Location::unquoted(PathBuf::from(source), span),
+                    },
+                    coordinates: Some(coordinates),
+                });
+            }
+        }
+    }
+
+    dependencies
+}
+
+/// Finds the remote repositories declared with `//> using repository ...`.
+pub fn find_repositories(code: &str) -> Vec<Repository> {
+    REPOSITORY_RE
+        .captures_iter(code)
+        .flat_map(|captures| {
+            captures[1]
+                .split([' ', ','])
+                .map(|value| value.trim_matches(|c| c == '"' || c == '`').to_string())
+                .filter(|value| is_remote(value))
+                .collect::<Vec<_>>()
+        })
+        .map(|url| {
+            let url = url.trim_end_matches('/');
+            Repository::new(url, url)
+        })
+        .collect()
+}
+
+/// The scope of a `test.dep` or `compileOnly.dep` directive.
+fn configuration(key: &str) -> Option<String> {
+    key.split_once('.').map(|(scope, _)| scope.to_string())
+}
+
+fn unscoped(key: &str) -> &str {
+    key.strip_prefix("test.")
+        .or_else(|| key.strip_prefix("compileOnly."))
+        .unwrap_or(key)
+}
+
+/// Whether the code contains any `//> using` directives.
+pub fn has_using_directives(code: &str) -> bool {
+    code.lines()
+        .any(|line| line.trim_start().starts_with("//> using"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Artifact, CrossVersion, Group};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_using_directives() {
+        let code = r#"//> using scala 3.3.1
+//> using dep "com.lihaoyi::os-lib:0.9.1"
+//> using deps com.lihaoyi::upickle:3.1.0, org.postgresql:postgresql:42.5.1
+//> using test.dep org.scalameta::munit::1.0.0
+//> using options -deprecation
+// using dep "not::a:directive"
+
+@main def hello() = println("hello")
+"#;
+
+        let source = PathBuf::from("project.scala");
+        let dependencies = parse_using_directives(&source, code);
+
+        let dependency = |group: &str, artifact: &str, cross: CrossVersion, version: &str| {
+            let start = code.find(&format!(":{}", version)).unwrap() + 1;
+            let artifact_start = code[..start].rfind(artifact).unwrap();
+            let group_start = code[..artifact_start].rfind(group).unwrap();
+            Dependency {
+                group: Group::new(group),
+                artifact: Artifact::new(artifact),
+                kind: DependencyKind::Library,
+                cross,
+                configuration: None,
+                classifier: None,
+                version: WithLocation {
+                    value: Version::new(version),
+                    location: Location::unquoted(
+                        source.clone(),
+                        Span::new(start, start + version.len()),
+                    ),
+                },
+                coordinates: Some(CoordinateLocation::unquoted(
+                    source.clone(),
+                    Span::new(group_start, group_start + group.len()),
+                    Span::new(artifact_start, artifact_start + artifact.len()),
+                )),
+            }
+        };
+
+        let expected_dependencies = vec![
+            Dependency {
+                group: Group::new("org.scala-lang"),
+                artifact: Artifact::new("scala3-library_3"),
+                kind: DependencyKind::Toolchain,
+                cross: CrossVersion::Disabled,
+                configuration: None,
+                classifier: None,
+                version: WithLocation {
+                    value: Version::new("3.3.1"),
+                    location: Location::unquoted(source.clone(), Span::new(16, 21)),
+                },
+                coordinates: None,
+            },
+            dependency("com.lihaoyi", "os-lib", CrossVersion::Binary, "0.9.1"),
+            dependency("com.lihaoyi", "upickle", CrossVersion::Binary, "3.1.0"),
+            dependency(
+                "org.postgresql",
+                "postgresql",
+                CrossVersion::Disabled,
+                "42.5.1",
+            ),
+            Dependency {
+                configuration: Some("test".to_string()),
+                ..dependency("org.scalameta", "munit", CrossVersion::Platform, "1.0.0")
+            },
+        ];
+        assert_eq!(dependencies, expected_dependencies);
+    }
+
+    #[test]
+    fn test_find_repositories() {
+        let code = "//> using repository https://maven.example.com/releases/ sonatype:snapshots\n";
+        assert_eq!(
+            find_repositories(code),
+            vec![Repository::new(
+                "https://maven.example.com/releases",
+                "https://maven.example.com/releases"
+            )]
+        );
+    }
+
+    #[test]
+    fn test_multiple_scala_versions() {
+        let code = "//> using scala \"3.3.1\" \"2.13.12\"\n";
+        let versions: Vec<_> = parse_using_directives(&PathBuf::from("a.sc"), code)
+            .into_iter()
+            .map(|d| (d.artifact.value, d.version.location.span))
+            .collect();
+
+        assert_eq!(
+            versions,
+            vec![
+                ("scala3-library_3".to_string(), Span::new(17, 22)),
+                ("scala-library".to_string(), Span::new(25, 32)),
+            ]
+        );
+    }
+}
@@ -10,6 +10,30 @@ impl Span {
     pub fn new(start: usize, end: usize) -> Self {
         Span { start, end }
     }
+
+    /// The span shifted by `offset`, e.g. from a string's contents to the whole file.
+    pub fn offset(&self, offset: usize) -> Self {
+        Span::new(self.start + offset, self.end + offset)
+    }
+}
+
+/// A 1-based line and column, with columns counted in characters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LineColumn {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl LineColumn {
+    /// Converts a byte offset into `code` into a line and column.
+    pub fn from_offset(code: &str, offset: usize) -> Self {
+        let before = &code[..offset.min(code.len())];
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        LineColumn {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -94,4 +118,21 @@ mod tests {
         let result = Edit::apply_edits(edits, original);
         assert_eq!(result, "Hi, Rust!");
     }
+
+    #[test]
+    fn test_line_column_from_offset() {
+        let code = "val a = 1\nval é = \"2.0.0\"\n";
+        assert_eq!(
+            LineColumn::from_offset(code, 0),
+            LineColumn { line: 1, column: 1 }
+        );
+        assert_eq!(
+            LineColumn::from_offset(code, 19),
+            LineColumn { line: 2, column: 9 }
+        );
+        assert_eq!(
+            LineColumn::from_offset(code, code.len()),
+            LineColumn { line: 3, column: 1 }
+        );
+    }
 }
@@ -0,0 +1,536 @@
+use std::{collections::HashMap, path::Path};
+
+use tree_sitter::Node;
+
+use super::{extract_text, parse_def, parse_val, WithLocation};
+
+/// The val (and parameterless def) definitions of a build, keyed by their fully
+/// qualified path: the package, the enclosing objects and the name, e.g.
+/// `Versions.zio` or `build.Dependencies.Versions.zio`. Definitions in `.sbt`
+/// files are only visible within their file, so their path starts with the file,
+/// e.g. `<build.sbt>.zioVersion`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SymbolTable {
+    definitions: HashMap<String, Definition>,
+}
+
+/// A definition's value, along with the scope it is written in, which
+/// references inside the value are resolved from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Definition {
+    pub value: WithLocation<String>,
+    pub scope: Scope,
+    /// Whether the value is a string literal, rather than an expression such as
+    /// a reference to another definition.
+    pub is_string: bool,
+}
+
+impl SymbolTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records every definition in a file. Definitions in the same place of two
+    /// Scala files are merged, the last one winning.
+    pub fn parse(&mut self, source: &Path, node: Node, code: &str) {
+        let mut path = file_scope(source).into_iter().collect();
+        self.parse_node(source, node, code, &mut path);
+    }
+
+    fn parse_node(&mut self, source: &Path, node: Node, code: &str, path: &mut Vec<String>) {
+        // parameterless defs, as commonly used for versions in Mill builds
+        if node.kind() == "function_definition" && node.child_by_field_name("parameters").is_none()
+        {
+            if let Some((name, value)) = parse_def(source, node, code) {
+                let scope = Scope::at(source, node, code);
+                self.insert(qualify(path, &name), value, scope, true);
+                return;
+            }
+        }
+        if node.kind() == "val_definition" {
+            if let Some((name, value)) = parse_val(source, node, code) {
+                let scope = Scope::at(source, node, code);
+                let is_string = node
+                    .child_by_field_name("value")
+                    .is_some_and(|value| value.kind() == "string");
+                self.insert(qualify(path, &name), value, scope, is_string);
+                return;
+            }
+        }
+
+        let depth = path.len();
+        if let "object_definition" | "class_definition" | "trait_definition" | "package_clause" =
+            node.kind()
+        {
+            if let Some(name) = node.child_by_field_name("name") {
+                path.extend(segments(name, code));
+            }
+        }
+        for child in node.named_children(&mut node.walk()) {
+            // `package a.b` without a body applies to the rest of the file
+            if child.kind() == "package_clause" && child.child_by_field_name("body").is_none() {
+                path.extend(
+                    child
+                        .child_by_field_name("name")
+                        .map(|name| segments(name, code))
+                        .unwrap_or_default(),
+                );
+                continue;
+            }
+            self.parse_node(source, child, code, path);
+        }
+        path.truncate(depth);
+    }
+
+    fn insert(
+        &mut self,
+        qualified_path: String,
+        value: WithLocation<String>,
+        scope: Scope,
+        is_string: bool,
+    ) {
+        self.definitions.insert(
+            qualified_path,
+            Definition {
+                value,
+                scope,
+                is_string,
+            },
+        );
+    }
+
+    /// The definition at exactly this qualified path.
+    pub fn get(&self, qualified_path: &str) -> Option<&WithLocation<String>> {
+        self.definitions
+            .get(qualified_path)
+            .map(|definition| &definition.value)
+    }
+
+    /// Every definition's value, by qualified path.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &WithLocation<String>)> {
+        self.definitions
+            .iter()
+            .map(|(path, definition)| (path, &definition.value))
+    }
+
+    /// Resolves a reference such as `zio` or `Versions.zio`, as written at the
+    /// given scope: relative to the enclosing objects and packages, innermost
+    /// first, then through the imports in effect. Failing that, the only
+    /// definition whose path ends with the reference is used, leaving out those
+    /// of other `.sbt` files; an ambiguous reference resolves to nothing rather
+    /// than to an arbitrary definition.
+    pub fn resolve(&self, scope: &Scope, reference: &str) -> Option<&WithLocation<String>> {
+        self.resolve_definition(scope, reference)
+            .map(|definition| &definition.value)
+    }
+
+    /// Like [`SymbolTable::resolve`], but to the whole definition, scope
+    /// included.
+    pub fn resolve_definition(&self, scope: &Scope, reference: &str) -> Option<&Definition> {
+        let relative = |path: &str| {
+            scope
+                .prefixes()
+                .find_map(|prefix| self.definitions.get(&qualify_str(&prefix, path)))
+        };
+        if let Some(definition) = relative(reference) {
+            return Some(definition);
+        }
+
+        let (head, rest) = match reference.split_once('.') {
+            Some((head, rest)) => (head, Some(rest)),
+            None => (reference, None),
+        };
+        for import in &scope.imports {
+            let target = match import {
+                Import::Name { path, alias } if alias == head => match rest {
+                    Some(rest) => format!("{}.{}", path, rest),
+                    None => path.clone(),
+                },
+                Import::Wildcard(path) => format!("{}.{}", path, reference),
+                Import::Name { .. } => continue,
+            };
+            if let Some(definition) = relative(&target) {
+                return Some(definition);
+            }
+        }
+
+        let suffix = format!(".{}", reference);
+        let mut candidates = self
+            .definitions
+            .iter()
+            .filter(|(path, _)| *path == reference || path.ends_with(&suffix))
+            .filter(|(path, _)| {
+                file_scope_of(path).is_none_or(|file| scope.path.first().is_some_and(|f| f == file))
+            });
+        match (candidates.next(), candidates.next()) {
+            (Some((_, definition)), None) => Some(definition),
+            _ => None,
+        }
+    }
+}
+
+/// What a reference can see from where it is written: the packages and
+/// objects enclosing it, and the imports before it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Scope {
+    /// The enclosing packages and objects, outermost first.
+    path: Vec<String>,
+    /// The imports in effect, the ones that shadow the others first.
+    imports: Vec<Import>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Import {
+    /// `import a.b.c` or `import a.b.{c => d}`
+    Name { path: String, alias: String },
+    /// `import a.b._` or `import a.b.*`
+    Wildcard(String),
+}
+
+impl Scope {
+    /// The top level of a file without a package.
+    pub fn root() -> Scope {
+        Scope::default()
+    }
+
+    /// The scope of the given node, in the file at `source`.
+    pub fn at(source: &Path, node: Node, code: &str) -> Scope {
+        // Collected innermost first, one entry per level of the tree.
+        let mut levels: Vec<Vec<String>> = Vec::new();
+        let mut imports = Vec::new();
+
+        let mut child = node;
+        while let Some(parent) = child.parent() {
+            let mut level = Vec::new();
+            let mut level_imports = Vec::new();
+            for sibling in parent.named_children(&mut parent.walk()) {
+                if sibling.start_byte() >= child.start_byte() {
+                    break;
+                }
+                match sibling.kind() {
+                    "import_declaration" => level_imports.extend(Import::parse(sibling, code)),
+                    "package_clause" if sibling.child_by_field_name("body").is_none() => {
+                        if let Some(name) = sibling.child_by_field_name("name") {
+                            level.extend(segments(name, code));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            // Later imports shadow earlier ones.
+            imports.extend(level_imports.into_iter().rev());
+
+            if let "object_definition" | "class_definition" | "trait_definition"
+            | "package_clause" = parent.kind()
+            {
+                if let Some(name) = parent.child_by_field_name("name") {
+                    let mut names = segments(name, code);
+                    names.extend(level);
+                    level = names;
+                }
+            }
+            levels.push(level);
+            child = parent;
+        }
+
+        Scope {
+            path: file_scope(source)
+                .into_iter()
+                .chain(levels.into_iter().rev().flatten())
+                .collect(),
+            imports,
+        }
+    }
+
+    /// Every path a reference may be relative to, innermost first, ending
+    /// with the root.
+    fn prefixes(&self) -> impl Iterator<Item = String> + '_ {
+        (0..=self.path.len())
+            .rev()
+            .map(|len| self.path[..len].join("."))
+    }
+}
+
+impl Import {
+    fn parse(node: Node, code: &str) -> Vec<Import> {
+        let path: Vec<String> = node
+            .children_by_field_name("path", &mut node.walk())
+            .filter(|segment| segment.kind() == "identifier")
+            .map(|segment| extract_text(segment, code))
+            .filter(|segment| segment != "_root_")
+            .collect();
+        let mut imports = Vec::new();
+        let mut has_selectors = false;
+        for child in node.named_children(&mut node.walk()) {
+            match child.kind() {
+                "namespace_wildcard" => {
+                    has_selectors = true;
+                    imports.push(Import::Wildcard(path.join(".")));
+                }
+                "namespace_selectors" => {
+                    has_selectors = true;
+                    for selector in child.named_children(&mut child.walk()) {
+                        imports.extend(Import::selector(&path, selector, code));
+                    }
+                }
+                _ => {}
+            }
+        }
+        if !has_selectors {
+            if let Some(alias) = path.last() {
+                imports.push(Import::Name {
+                    path: path.join("."),
+                    alias: alias.clone(),
+                });
+            }
+        }
+        imports
+    }
+
+    /// One selector of `import a.b.{c, d => e, _}`.
+    fn selector(path: &[String], node: Node, code: &str) -> Option<Import> {
+        let prefix = path.join(".");
+        match node.kind() {
+            "namespace_wildcard" => Some(Import::Wildcard(prefix)),
+            "identifier" => {
+                let name = extract_text(node, code);
+                Some(Import::Name {
+                    path: qualify_str(&prefix, &name),
+                    alias: name,
+                })
+            }
+            "arrow_renamed_identifier" | "as_renamed_identifier" => {
+                let name = extract_text(node.child_by_field_name("name")?, code);
+                let alias = extract_text(node.child_by_field_name("alias")?, code);
+                // `c => _` hides `c`
+                (alias != "_").then(|| Import::Name {
+                    path: qualify_str(&prefix, &name),
+                    alias,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The scope a file's definitions are kept in apart from those of other files,
+/// which sbt does for `.sbt` files.
+fn file_scope(source: &Path) -> Option<String> {
+    source
+        .extension()
+        .is_some_and(|extension| extension == "sbt")
+        .then(|| format!("<{}>", source.display()))
+}
+
+/// The file scope a qualified path starts with, if any.
+fn file_scope_of(path: &str) -> Option<&str> {
+    if !path.starts_with('<') {
+        return None;
+    }
+    path.find(">.").map(|end| &path[..=end])
+}
+
+/// The identifiers of a dotted name, e.g. the `a.b` of `package a.b`.
+fn segments(node: Node, code: &str) -> Vec<String> {
+    let mut cursor = node.walk();
+    let identifiers: Vec<String> = node
+        .named_children(&mut cursor)
+        .filter(|child| child.kind() == "identifier")
+        .map(|child| extract_text(child, code))
+        .collect();
+    if identifiers.is_empty() {
+        vec![extract_text(node, code)]
+    } else {
+        identifiers
+    }
+}
+
+fn qualify(path: &[String], name: &str) -> String {
+    qualify_str(&path.join("."), name)
+}
+
+fn qualify_str(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", prefix, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::parser::parse_tree;
+    use pretty_assertions::assert_eq;
+
+    /// The scope of whatever is written at the given byte range of `code`.
+    fn scope_at(source: &Path, code: &str, start: usize, end: usize) -> Scope {
+        let tree = parse_tree(code);
+        tree.root_node()
+            .descendant_for_byte_range(start, end)
+            .map_or_else(Scope::root, |node| Scope::at(source, node, code))
+    }
+
+    fn table(files: &[(&str, &str)]) -> SymbolTable {
+        let mut table = SymbolTable::new();
+        for (path, code) in files {
+            table.parse(&PathBuf::from(path), parse_tree(code).root_node(), code);
+        }
+        table
+    }
+
+    /// Resolves `reference` as written at its last occurrence in `code`, the
+    /// contents of the file at `source`.
+    fn resolve_in(
+        table: &SymbolTable,
+        source: &str,
+        code: &str,
+        reference: &str,
+    ) -> Option<String> {
+        let start = code.rfind(reference).unwrap();
+        let scope = scope_at(Path::new(source), code, start, start + reference.len());
+        table
+            .resolve(&scope, reference)
+            .map(|definition| definition.value.clone())
+    }
+
+    fn resolve(table: &SymbolTable, code: &str, reference: &str) -> Option<String> {
+        resolve_in(table, "project/Build.scala", code, reference)
+    }
+
+    #[test]
+    fn test_qualified_paths() {
+        let table = table(&[
+            (
+                "project/Versions.scala",
+                r#"
+                package build
+                object Versions { val zio = "2.1.0" }
+                "#,
+            ),
+            (
+                "project/OtherVersions.scala",
+                r#"
+                object OtherVersions {
+                  val zio = "1.0.18"
+                  object Nested { lazy val zio = "1.0.0" }
+                }
+                "#,
+            ),
+        ]);
+        assert_eq!(
+            table.get("build.Versions.zio").map(|d| d.value.as_str()),
+            Some("2.1.0")
+        );
+        assert_eq!(
+            table.get("OtherVersions.zio").map(|d| d.value.as_str()),
+            Some("1.0.18")
+        );
+        assert_eq!(
+            table
+                .get("OtherVersions.Nested.zio")
+                .map(|d| d.value.as_str()),
+            Some("1.0.0")
+        );
+
+        let code = r#"val b = OtherVersions.zio; val a = Versions.zio; val c = zio"#;
+        assert_eq!(resolve(&table, code, "Versions.zio"), Some("2.1.0".into()));
+        assert_eq!(
+            resolve(&table, code, "OtherVersions.zio"),
+            Some("1.0.18".into())
+        );
+        // Three definitions are called `zio`.
+        assert_eq!(resolve(&table, code, "zio"), None);
+    }
+
+    #[test]
+    fn test_resolve_through_scopes_and_imports() {
+        let table = table(&[(
+            "project/Dependencies.scala",
+            r#"
+            package build.deps
+            object Versions {
+              val zio = "2.1.0"
+              val zioJson = "0.7.0"
+            }
+            object Legacy {
+              val zio = "1.0.18"
+              val zioTest = zio
+            }
+            "#,
+        )]);
+
+        let inside = r#"
+            package build.deps
+            object Legacy { val x = zio }
+            "#;
+        assert_eq!(resolve(&table, inside, "zio"), Some("1.0.18".into()));
+
+        let wildcard = r#"
+            import build.deps.Versions._
+            val x = zio
+            "#;
+        assert_eq!(resolve(&table, wildcard, "zio"), Some("2.1.0".into()));
+
+        let shadowed = r#"
+            import build.deps.Versions._
+            import build.deps.Legacy._
+            val x = zio
+            "#;
+        assert_eq!(resolve(&table, shadowed, "zio"), Some("1.0.18".into()));
+
+        let renamed = r#"
+            import build.deps.Versions.{zio => zioVersion, zioJson}
+            val x = Seq(zioVersion, zioJson)
+            "#;
+        assert_eq!(resolve(&table, renamed, "zioVersion"), Some("2.1.0".into()));
+        assert_eq!(resolve(&table, renamed, "zioJson"), Some("0.7.0".into()));
+
+        let object = r#"
+            import build.deps.Legacy
+            val x = Legacy.zio
+            "#;
+        assert_eq!(resolve(&table, object, "Legacy.zio"), Some("1.0.18".into()));
+
+        let package = r#"
+            package build
+            object Build { val x = deps.Versions.zio }
+            "#;
+        assert_eq!(
+            resolve(&table, package, "deps.Versions.zio"),
+            Some("2.1.0".into())
+        );
+    }
+
+    #[test]
+    fn test_sbt_files_keep_their_definitions_apart() {
+        let build = r#"val zioVersion = "2.1.0"; val x = zioVersion"#;
+        let other = r#"val zioVersion = "1.0.18"; val y = zioVersion"#;
+        let table = table(&[
+            ("build.sbt", build),
+            ("other.sbt", other),
+            ("project/plugins.sbt", r#"val scalafmtVersion = "2.5.2""#),
+        ]);
+
+        assert_eq!(
+            resolve_in(&table, "build.sbt", build, "zioVersion"),
+            Some("2.1.0".into())
+        );
+        assert_eq!(
+            resolve_in(&table, "other.sbt", other, "zioVersion"),
+            Some("1.0.18".into())
+        );
+        // not even as the only definition of its name
+        assert_eq!(
+            resolve_in(
+                &table,
+                "build.sbt",
+                "val z = scalafmtVersion",
+                "scalafmtVersion"
+            ),
+            None
+        );
+    }
+}
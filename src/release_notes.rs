@@ -0,0 +1,467 @@
+use std::sync::LazyLock;
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use reqwest::Client;
+use serde::{de::DeserializeOwned, Deserialize};
+
+use crate::model::Version;
+
+/// The files a changelog is looked for in, when the repository has no GitHub releases.
+const CHANGELOG_FILES: [&str; 3] = ["CHANGELOG.md", "RELEASE_NOTES.md", "RELEASE_NOTES"];
+
+/// The most items GitHub lists per page.
+const PER_PAGE: usize = 100;
+
+/// How many pages of releases or tags are read at most.
+const MAX_PAGES: usize = 10;
+
+static HEADING_VERSION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^#{1,4}\s+.*?\bv?(\d+\.\d+(?:\.\d+)?(?:-[0-9A-Za-z.-]+)?)").unwrap()
+});
+
+/// The notes of a single release.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReleaseNote {
+    pub version: Version,
+    pub title: String,
+    pub body: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct Release {
+    tag_name: String,
+    name: Option<String>,
+    body: Option<String>,
+    #[serde(default)]
+    draft: bool,
+}
+
+/// Fetches release notes from a project's GitHub releases, falling back to the
+/// changelog in its source repository.
+#[derive(Debug, Clone)]
+pub struct ReleaseNotesClient {
+    client: Client,
+    /// `https://api.github.com`, or a GitHub Enterprise or test stand-in.
+    api_url: String,
+    token: Option<String>,
+}
+
+impl ReleaseNotesClient {
+    /// Authenticates with `GITHUB_TOKEN` when set, which raises GitHub's rate limit.
+    pub fn new(client: Client, api_url: &str) -> Self {
+        ReleaseNotesClient {
+            client,
+            api_url: api_url.trim_end_matches('/').to_string(),
+            token: std::env::var("GITHUB_TOKEN").ok().filter(|t| !t.is_empty()),
+        }
+    }
+
+    /// The notes of every release after `current`, up to and including `target`,
+    /// newest first. `repository_url` is the project's GitHub URL, as found in its POM.
+    pub async fn fetch(
+        &self,
+        repository_url: &str,
+        current: &Version,
+        target: &Version,
+    ) -> Result<Vec<ReleaseNote>> {
+        let (owner, repo) = github_repository(repository_url)
+            .with_context(|| format!("{} is not a GitHub repository", repository_url))?;
+        let in_range = |notes: Vec<ReleaseNote>| -> Vec<ReleaseNote> {
+            let mut notes: Vec<ReleaseNote> = notes
+                .into_iter()
+                .filter(|note| &note.version > current && &note.version <= target)
+                .collect();
+            notes.sort_by(|a, b| b.version.cmp(&a.version));
+            notes.dedup_by(|a, b| a.version == b.version);
+            notes
+        };
+
+        let releases = self.releases(&owner, &repo).await;
+        if let Ok(releases) = &releases {
+            let notes = in_range(releases.clone());
+            if !notes.is_empty() {
+                return Ok(notes);
+            }
+        }
+        match self.changelog(&owner, &repo).await {
+            Ok(changelog) => Ok(in_range(changelog)),
+            Err(changelog_err) => releases
+                .map(|_| vec![])
+                .with_context(|| format!("No changelog either: {:#}", changelog_err)),
+        }
+    }
+
+    /// The tag the version was released under. The POM's `<scm><tag>`, if any, is
+    /// preferred, then `v1.2.3` and `1.2.3`, which are looked up directly; only
+    /// failing these are the repository's tags listed, for e.g. `project-1.2.3`.
+    pub async fn release_tag(
+        &self,
+        repository_url: &str,
+        version: &Version,
+        hint: Option<&str>,
+    ) -> Result<Option<String>> {
+        let (owner, repo) = github_repository(repository_url)
+            .with_context(|| format!("{} is not a GitHub repository", repository_url))?;
+        let candidates = hint
+            .map(str::to_string)
+            .into_iter()
+            .chain([format!("v{}", version), version.to_string()]);
+        for candidate in candidates {
+            let path = format!("/repos/{}/{}/git/ref/tags/{}", owner, repo, candidate);
+            if self.get(&path, "application/vnd.github+json").await.is_ok() {
+                return Ok(Some(candidate));
+            }
+        }
+
+        #[derive(Deserialize, Debug)]
+        struct Tag {
+            name: String,
+        }
+        let tags: Vec<Tag> = self
+            .get_pages(&format!("/repos/{}/{}/tags", owner, repo), "tags")
+            .await?;
+        let tags: Vec<String> = tags.into_iter().map(|tag| tag.name).collect();
+        Ok(detect_tag(&tags, version, hint))
+    }
+
+    async fn get(&self, path: &str, accept: &str) -> Result<reqwest::Response> {
+        let url = format!("{}{}", self.api_url, path);
+        let mut request = self
+            .client
+            .get(&url)
+            .header("User-Agent", "Rust reqwest client")
+            .header("Accept", accept);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("Failed to send request to {}", url))?;
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "Request to {} failed with status: {}",
+                url,
+                response.status()
+            );
+        }
+        Ok(response)
+    }
+
+    /// Every item of a paginated GitHub listing, up to [`MAX_PAGES`] pages.
+    async fn get_pages<T: DeserializeOwned>(&self, path: &str, what: &str) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+        for page in 1..=MAX_PAGES {
+            let batch: Vec<T> = self
+                .get(
+                    &format!("{}?per_page={}&page={}", path, PER_PAGE, page),
+                    "application/vnd.github+json",
+                )
+                .await?
+                .json()
+                .await
+                .with_context(|| format!("Failed to parse GitHub {}", what))?;
+            let is_last = batch.len() < PER_PAGE;
+            items.extend(batch);
+            if is_last {
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    async fn releases(&self, owner: &str, repo: &str) -> Result<Vec<ReleaseNote>> {
+        let releases: Vec<Release> = self
+            .get_pages(&format!("/repos/{}/{}/releases", owner, repo), "releases")
+            .await?;
+
+        Ok(releases
+            .into_iter()
+            .filter(|release| !release.draft)
+            .filter_map(|release| {
+                Some(ReleaseNote {
+                    version: version_from_tag(&release.tag_name)?,
+                    title: release
+                        .name
+                        .filter(|name| !name.trim().is_empty())
+                        .unwrap_or(release.tag_name),
+                    body: release.body.unwrap_or_default(),
+                })
+            })
+            .collect())
+    }
+
+    async fn changelog(&self, owner: &str, repo: &str) -> Result<Vec<ReleaseNote>> {
+        let mut last_error = None;
+        for file in CHANGELOG_FILES {
+            let path = format!("/repos/{}/{}/contents/{}", owner, repo, file);
+            match self.get(&path, "application/vnd.github.raw").await {
+                Ok(response) => {
+                    let markdown = response.text().await?;
+                    return Ok(parse_changelog(&markdown));
+                }
+                Err(err) => last_error = Some(err),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No changelog found")))
+    }
+}
+
+/// The owner and name of a GitHub repository, from URLs such as
+/// `https://github.com/zio/zio`, `github.com/zio/zio.git` or `git@github.com:zio/zio.git`.
+pub fn github_repository(url: &str) -> Option<(String, String)> {
+    let (_, path) = url.split_once("github.com")?;
+    let mut segments = path
+        .trim_start_matches([':', '/'])
+        .split('/')
+        .filter(|segment| !segment.is_empty());
+    let owner = segments.next()?;
+    let repo = segments.next()?.trim_end_matches(".git");
+    Some((owner.to_string(), repo.to_string()))
+}
+
+/// The version a release tag names, e.g. `v2.0.1`, `zio-json-0.6.0` or `2.0.1`.
+fn version_from_tag(tag: &str) -> Option<Version> {
+    let start = tag.find(|c: char| c.is_ascii_digit())?;
+    let version = &tag[start..];
+    version
+        .contains('.')
+        .then(|| Version::new(version))
+        .filter(|version| matches!(version, Version::SemVer { .. }))
+}
+
+/// Picks the tag of a release among a repository's tags, whichever the project's
+/// pattern: `v1.2.3`, `1.2.3` or `project-1.2.3`.
+pub fn detect_tag(tags: &[String], version: &Version, hint: Option<&str>) -> Option<String> {
+    let version = version.to_string();
+    let is_tag = |tag: &str| tags.iter().any(|t| t == tag);
+    if let Some(hint) = hint.filter(|hint| is_tag(hint)) {
+        return Some(hint.to_string());
+    }
+    [format!("v{}", version), version.clone()]
+        .into_iter()
+        .find(|tag| is_tag(tag))
+        .or_else(|| {
+            tags.iter()
+                .find(|tag| {
+                    tag.strip_suffix(version.as_str())
+                        .is_some_and(|prefix| prefix.ends_with(['-', '_', '/']))
+                })
+                .cloned()
+        })
+}
+
+/// Splits a Markdown changelog into releases, one per heading that names a version,
+/// e.g. `## [2.0.1] - 2024-05-07` or `# Version 2.0.1`.
+pub fn parse_changelog(markdown: &str) -> Vec<ReleaseNote> {
+    let mut notes: Vec<ReleaseNote> = Vec::new();
+    for line in markdown.lines() {
+        if let Some(captures) = HEADING_VERSION_RE.captures(line) {
+            notes.push(ReleaseNote {
+                version: Version::new(&captures[1]),
+                title: line.trim_start_matches('#').trim().to_string(),
+                body: String::new(),
+            });
+        } else if let Some(note) = notes.last_mut() {
+            note.body.push_str(line);
+            note.body.push('\n');
+        }
+    }
+    for note in &mut notes {
+        note.body = note.body.trim().to_string();
+    }
+    notes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    #[test]
+    fn test_parse_changelog() {
+        let changelog = "\
+# Changelog
+
+## [2.1.0] - 2024-05-07
+### Added
+- Streams
+
+## [2.0.1]
+- Fixed a leak
+";
+        assert_eq!(
+            parse_changelog(changelog),
+            vec![
+                ReleaseNote {
+                    version: Version::new("2.1.0"),
+                    title: "[2.1.0] - 2024-05-07".to_string(),
+                    body: "### Added\n- Streams".to_string(),
+                },
+                ReleaseNote {
+                    version: Version::new("2.0.1"),
+                    title: "[2.0.1]".to_string(),
+                    body: "- Fixed a leak".to_string(),
+                },
+            ]
+        );
+
+        assert_eq!(
+            github_repository("git@github.com:zio/zio-json.git"),
+            Some(("zio".to_string(), "zio-json".to_string()))
+        );
+        assert_eq!(github_repository("https://gitlab.com/zio/zio"), None);
+        assert_eq!(
+            version_from_tag("zio-json-0.6.0"),
+            Some(Version::new("0.6.0"))
+        );
+        assert_eq!(version_from_tag("nightly"), None);
+
+        let tags: Vec<String> = ["v1.2.4", "1.2.3", "core-1.2.2", "v1.2.2-RC1"]
+            .iter()
+            .map(|tag| tag.to_string())
+            .collect();
+        let tag = |version: &str, hint| detect_tag(&tags, &Version::new(version), hint);
+        assert_eq!(tag("1.2.4", None), Some("v1.2.4".to_string()));
+        assert_eq!(tag("1.2.3", None), Some("1.2.3".to_string()));
+        assert_eq!(tag("1.2.2", None), Some("core-1.2.2".to_string()));
+        assert_eq!(
+            tag("1.2.3", Some("release-1.2.3")),
+            Some("1.2.3".to_string())
+        );
+        assert_eq!(tag("1.2.5", None), None);
+    }
+
+    /// Serves canned responses by path, standing in for the GitHub API.
+    async fn serve(routes: Vec<(&'static str, String)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = vec![0; 4096];
+                let read = socket.read(&mut request).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&request[..read]).to_string();
+                let path = request.split_whitespace().nth(1).unwrap_or("").to_string();
+                let response = match routes.iter().find(|(route, _)| *route == path) {
+                    Some((_, body)) => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    ),
+                    None => {
+                        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                            .to_string()
+                    }
+                };
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        format!("http://{}", address)
+    }
+
+    #[tokio::test]
+    async fn test_fetch_release_notes() -> Result<()> {
+        let api_url = serve(vec![
+            (
+                "/repos/zio/zio/releases?per_page=100&page=1",
+                r#"[
+                    {"tag_name": "v2.1.0", "name": "ZIO 2.1.0", "body": "Streams"},
+                    {"tag_name": "v2.0.1", "name": "", "body": "Fixes"},
+                    {"tag_name": "v2.0.0", "name": "ZIO 2", "body": "Old"},
+                    {"tag_name": "v3.0.0", "name": "Draft", "body": "", "draft": true}
+                ]"#
+                .to_string(),
+            ),
+            (
+                "/repos/zio/zio-json/contents/CHANGELOG.md",
+                "## 0.7.0\nNew codecs\n## 0.6.0\nOld codecs\n".to_string(),
+            ),
+        ])
+        .await;
+        let client = ReleaseNotesClient::new(Client::new(), &api_url);
+
+        let notes = client
+            .fetch(
+                "https://github.com/zio/zio",
+                &Version::new("2.0.0"),
+                &Version::new("2.1.0"),
+            )
+            .await?;
+        assert_eq!(
+            notes
+                .iter()
+                .map(|note| (note.version.to_string(), note.title.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("2.1.0".to_string(), "ZIO 2.1.0"),
+                ("2.0.1".to_string(), "v2.0.1")
+            ]
+        );
+
+        let notes = client
+            .fetch(
+                "https://github.com/zio/zio-json",
+                &Version::new("0.6.0"),
+                &Version::new("0.7.0"),
+            )
+            .await?;
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].body, "New codecs");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_release_notes_span_pages() -> Result<()> {
+        let newer: Vec<String> = (1..=100)
+            .rev()
+            .map(|patch| format!(r#"{{"tag_name": "v2.0.{}", "body": ""}}"#, patch))
+            .collect();
+        let api_url = serve(vec![
+            (
+                "/repos/zio/zio/releases?per_page=100&page=1",
+                format!("[{}]", newer.join(",")),
+            ),
+            (
+                "/repos/zio/zio/releases?per_page=100&page=2",
+                r#"[{"tag_name": "v1.1.0", "name": "ZIO 1.1", "body": "Old"}]"#.to_string(),
+            ),
+            (
+                "/repos/zio/zio/tags?per_page=100&page=1",
+                r#"[{"name": "core-1.1.0"}]"#.to_string(),
+            ),
+            ("/repos/zio/zio/git/ref/tags/v2.0.7", "{}".to_string()),
+        ])
+        .await;
+        let client = ReleaseNotesClient::new(Client::new(), &api_url);
+
+        let notes = client
+            .fetch(
+                "https://github.com/zio/zio",
+                &Version::new("1.0.0"),
+                &Version::new("1.1.0"),
+            )
+            .await?;
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].title, "ZIO 1.1");
+
+        let tag = |version: &'static str| {
+            let client = client.clone();
+            async move {
+                client
+                    .release_tag("https://github.com/zio/zio", &Version::new(version), None)
+                    .await
+            }
+        };
+        assert_eq!(tag("2.0.7").await?, Some("v2.0.7".to_string()));
+        assert_eq!(tag("1.1.0").await?, Some("core-1.1.0".to_string()));
+        assert_eq!(tag("1.2.0").await?, None);
+
+        Ok(())
+    }
+}